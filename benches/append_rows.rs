@@ -0,0 +1,39 @@
+//! Compares pushing a full frame as one `append_data` call per row, each waiting for the bus,
+//! with `append_data_nowait` per row and a single `sync` at the end.
+//!
+//! Writes on the simulated bus are free and every `flush` costs a few microseconds, standing in
+//! for waiting on a DMA transfer to complete.
+//!
+//! Run with `cargo bench --no-default-features --features stack_alloc --bench append_rows`.
+
+mod common;
+
+use std::time::Duration;
+
+use atk_md0240::driver::Lcd;
+use common::{NoDelay, Pin, SimBus, bench};
+
+const ITERATIONS: u32 = 50;
+const ROW: [u8; 240 * 2] = [0x5A; 240 * 2];
+
+fn main() {
+    let bus = SimBus {
+        write_cost: Duration::ZERO,
+        flush_cost: Duration::from_micros(5),
+    };
+    let mut lcd = Lcd::init(bus, Pin, Pin, Pin, &mut NoDelay);
+
+    bench("append_data", ITERATIONS, || {
+        drop(lcd.begin_write(0, 0, 239, 319).unwrap());
+        for _ in 0..320 {
+            lcd.append_data(&ROW).unwrap();
+        }
+    });
+    bench("nowait+sync", ITERATIONS, || {
+        drop(lcd.begin_write(0, 0, 239, 319).unwrap());
+        for _ in 0..320 {
+            lcd.append_data_nowait(&ROW).unwrap();
+        }
+        lcd.sync().unwrap();
+    });
+}
@@ -0,0 +1,79 @@
+//! Host stand-ins for the bus, pins and delay, shared by the driver benchmarks
+
+use std::convert::Infallible;
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use embedded_hal::delay::DelayNs;
+use embedded_hal::digital::{self, OutputPin};
+use embedded_hal::spi::{self, SpiBus};
+
+/// Simulated SPI bus: moving bytes is free, but each `write` and `flush` busy-waits for a
+/// fixed time, standing in for DMA setup and for waiting on a transfer to complete
+pub struct SimBus {
+    pub write_cost: Duration,
+    pub flush_cost: Duration,
+}
+
+pub fn spin(duration: Duration) {
+    let start = Instant::now();
+    while start.elapsed() < duration {}
+}
+
+impl spi::ErrorType for SimBus {
+    type Error = Infallible;
+}
+
+impl SpiBus for SimBus {
+    fn read(&mut self, _words: &mut [u8]) -> Result<(), Infallible> {
+        Ok(())
+    }
+    fn write(&mut self, words: &[u8]) -> Result<(), Infallible> {
+        black_box(words);
+        spin(self.write_cost);
+        Ok(())
+    }
+    fn transfer(&mut self, _read: &mut [u8], _write: &[u8]) -> Result<(), Infallible> {
+        Ok(())
+    }
+    fn transfer_in_place(&mut self, _words: &mut [u8]) -> Result<(), Infallible> {
+        Ok(())
+    }
+    fn flush(&mut self) -> Result<(), Infallible> {
+        spin(self.flush_cost);
+        Ok(())
+    }
+}
+
+pub struct Pin;
+
+impl digital::ErrorType for Pin {
+    type Error = Infallible;
+}
+
+impl OutputPin for Pin {
+    fn set_low(&mut self) -> Result<(), Infallible> {
+        Ok(())
+    }
+    fn set_high(&mut self) -> Result<(), Infallible> {
+        Ok(())
+    }
+}
+
+pub struct NoDelay;
+
+impl DelayNs for NoDelay {
+    fn delay_ns(&mut self, _ns: u32) {}
+}
+
+/// Runs `f` `iterations` times and prints the average time per run
+pub fn bench(name: &str, iterations: u32, mut f: impl FnMut()) {
+    let start = Instant::now();
+    for _ in 0..iterations {
+        f();
+    }
+    println!(
+        "{name:<14} {:>10.1?} per frame",
+        start.elapsed() / iterations
+    );
+}
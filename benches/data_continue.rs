@@ -0,0 +1,64 @@
+//! Compares streaming a full frame as many small slices with `append_data_nowait`, which
+//! drives DC high before every slice, with a `WriteSession`, which drives it once per `RAMWR`
+//! and sends the remaining slices with `data_continue`.
+//!
+//! SPI writes and flushes are free on the simulated bus, while every DC pin write costs a
+//! fixed time, standing in for a GPIO access through a slow HAL or port expander.
+//!
+//! Run with `cargo bench --no-default-features --features stack_alloc --bench data_continue`.
+
+mod common;
+
+use std::convert::Infallible;
+use std::time::Duration;
+
+use atk_md0240::driver::Lcd;
+use common::{NoDelay, Pin, SimBus, bench, spin};
+use embedded_hal::digital::{self, OutputPin};
+
+const ITERATIONS: u32 = 50;
+/// Slice length in bytes: 32 pixels, so a frame takes 2400 slices
+const SLICE: [u8; 64] = [0x5A; 64];
+const SLICES: usize = 240 * 320 * 2 / SLICE.len();
+
+/// Data/command pin whose every write busy-waits for the given time
+struct SlowPin(Duration);
+
+impl digital::ErrorType for SlowPin {
+    type Error = Infallible;
+}
+
+impl OutputPin for SlowPin {
+    fn set_low(&mut self) -> Result<(), Infallible> {
+        spin(self.0);
+        Ok(())
+    }
+    fn set_high(&mut self) -> Result<(), Infallible> {
+        spin(self.0);
+        Ok(())
+    }
+}
+
+fn main() {
+    let bus = SimBus {
+        write_cost: Duration::ZERO,
+        flush_cost: Duration::ZERO,
+    };
+    let dc = SlowPin(Duration::from_nanos(500));
+    let mut lcd = Lcd::init(bus, Pin, dc, Pin, &mut NoDelay);
+
+    bench("nowait", ITERATIONS, || {
+        drop(lcd.begin_write(0, 0, 239, 319).unwrap());
+        for _ in 0..SLICES {
+            lcd.append_data_nowait(&SLICE).unwrap();
+        }
+        lcd.sync().unwrap();
+    });
+    bench("session", ITERATIONS, || {
+        let mut session = lcd.begin_write(0, 0, 239, 319).unwrap();
+        for _ in 0..SLICES {
+            session.push_slice(&SLICE).unwrap();
+        }
+        session.end().unwrap();
+    });
+}
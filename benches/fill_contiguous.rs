@@ -0,0 +1,40 @@
+//! Compares drawing a filled circle pixel by pixel through `draw_iter` with the
+//! `fill_contiguous` path that `embedded-graphics` uses for filled primitives.
+//!
+//! Run with `cargo bench --no-default-features --features stack_alloc`.
+
+use std::hint::black_box;
+use std::time::Instant;
+
+use atk_md0240::graphics::Display2in14;
+use embedded_graphics::{
+    pixelcolor::Rgb565,
+    prelude::*,
+    primitives::{Circle, PrimitiveStyle},
+};
+
+const ITERATIONS: u32 = 200;
+
+fn bench(name: &str, mut f: impl FnMut()) {
+    let start = Instant::now();
+    for _ in 0..ITERATIONS {
+        f();
+    }
+    println!(
+        "{name:<12} {:>10.1?} per iteration",
+        start.elapsed() / ITERATIONS
+    );
+}
+
+fn main() {
+    let mut display = Display2in14::new(Rgb565::BLACK);
+    let circle =
+        Circle::new(Point::new(20, 40), 200).into_styled(PrimitiveStyle::with_fill(Rgb565::RED));
+
+    bench("draw_iter", || {
+        display.draw_iter(black_box(&circle).pixels()).unwrap();
+    });
+    bench("contiguous", || {
+        black_box(&circle).draw(&mut display).unwrap();
+    });
+}
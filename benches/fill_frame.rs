@@ -0,0 +1,49 @@
+//! Compares filling the panel from a small repeated scratch buffer of various sizes with
+//! flushing a complete framebuffer.
+//!
+//! Each `write` on the simulated bus costs a fixed setup time, so the results show how the
+//! number of SPI transactions, which shrinks as the scratch buffer grows, affects a fill.
+//! Clocking the bytes out is not simulated; on hardware it adds about 31 ms per frame at
+//! 40 MHz to every variant, so a few hundred microseconds of per-write overhead is minor.
+//!
+//! Run with `cargo bench --no-default-features --features stack_alloc --bench fill_frame`.
+
+mod common;
+
+use std::time::Duration;
+
+use atk_md0240::driver::Lcd;
+use atk_md0240::graphics::Display2in14;
+use common::{NoDelay, Pin, SimBus, bench};
+use embedded_graphics::{pixelcolor::Rgb565, prelude::*};
+
+const ITERATIONS: u32 = 50;
+
+fn main() {
+    let bus = SimBus {
+        write_cost: Duration::from_micros(2),
+        flush_cost: Duration::ZERO,
+    };
+    let mut lcd = Lcd::init(bus, Pin, Pin, Pin, &mut NoDelay);
+    let color = Rgb565::CSS_TEAL.into_storage();
+    let display = Display2in14::new(Rgb565::CSS_TEAL);
+
+    bench("scratch 64", ITERATIONS, || {
+        lcd.fill_rect_with_scratch::<64>(0, 0, 240, 320, color)
+            .unwrap();
+    });
+    bench("scratch 256", ITERATIONS, || {
+        lcd.fill_rect_with_scratch::<256>(0, 0, 240, 320, color)
+            .unwrap();
+    });
+    bench("fill_frame", ITERATIONS, || {
+        lcd.fill_frame(color).unwrap();
+    });
+    bench("scratch 4096", ITERATIONS, || {
+        lcd.fill_rect_with_scratch::<4096>(0, 0, 240, 320, color)
+            .unwrap();
+    });
+    bench("flush", ITERATIONS, || {
+        lcd.flush(&display).unwrap();
+    });
+}
@@ -0,0 +1,149 @@
+//! Async driver for `embedded-hal-async` SPI buses
+use super::driver::DEFAULT_INIT;
+use super::error::{DriverError, Error};
+use super::graphics::Framebuffer;
+use super::interface::{Backlight, ResetPin, ResetTiming};
+use super::st7789v::*;
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+use embedded_hal::digital::OutputPin;
+use embedded_hal_async::{delay::DelayNs, spi::SpiBus};
+
+/// LCD driver whose SPI transfers are `.await`ed, for use with async executors such as Embassy.
+///
+/// It covers initialisation and framebuffer uploads; use the blocking
+/// [`Lcd`](crate::driver::Lcd) for the rest of the register set.
+pub struct AsyncLcd<SPI, RST, WR, PWR> {
+    /// SPI device
+    spi: SPI,
+    /// Pin for Reseting
+    rst: RST,
+    /// Data/Command Control Pin (High for data, Low for command)
+    wr: WR,
+    /// LCD backlight control pin (Low: Off, High: On)
+    pwr: PWR,
+}
+
+impl<SPI, RST, WR, PWR> AsyncLcd<SPI, RST, WR, PWR>
+where
+    SPI: SpiBus,
+    RST: ResetPin,
+    WR: OutputPin,
+    PWR: Backlight,
+{
+    /// Resets the panel with `reset_timing`, sends [`DEFAULT_INIT`] as
+    /// [`Lcd::init`](crate::driver::Lcd::init) does, then turns the backlight on.
+    pub async fn init(
+        spi: SPI,
+        rst: RST,
+        wr: WR,
+        pwr: PWR,
+        delay: &mut impl DelayNs,
+        reset_timing: ResetTiming,
+    ) -> Result<Self, DriverError<SPI, RST, WR, PWR>> {
+        let mut lcd = Self { spi, rst, wr, pwr };
+        if RST::CONNECTED {
+            lcd.rst.hold().map_err(Error::Reset)?;
+            delay.delay_us(reset_timing.pulse_us).await;
+            lcd.rst.release().map_err(Error::Reset)?;
+        } else {
+            lcd.write_command(Cmd::SWRESET.bits(), &[]).await?;
+        }
+        delay.delay_ms(reset_timing.wait_ms).await;
+        for step in DEFAULT_INIT {
+            lcd.write_command(step.cmd, step.data).await?;
+            if step.delay_ms > 0 {
+                delay.delay_ms(step.delay_ms).await;
+            }
+        }
+        lcd.write_command(Cmd::MADCTL.bits(), &[0x00]).await?;
+        lcd.pwr.set_level(u8::MAX).map_err(Error::Backlight)?;
+        Ok(lcd)
+    }
+    /// Sends a command byte followed by its parameter bytes.
+    ///
+    /// The command byte is flushed before DC is driven high for `data`.
+    pub async fn write_command(
+        &mut self,
+        command: u8,
+        data: &[u8],
+    ) -> Result<(), DriverError<SPI, RST, WR, PWR>> {
+        self.wr.set_low().map_err(Error::Pin)?;
+        self.spi.write(&[command]).await.map_err(Error::Spi)?;
+        self.spi.flush().await.map_err(Error::Spi)?;
+        if data.is_empty() {
+            return Ok(());
+        }
+        self.wr.set_high().map_err(Error::Pin)?;
+        self.spi.write(data).await.map_err(Error::Spi)?;
+        self.spi.flush().await.map_err(Error::Spi)
+    }
+    /// Sets the frame memory area (column and row address range), inclusive.
+    pub async fn set_frame_area(
+        &mut self,
+        start_x: u16,
+        start_y: u16,
+        end_x: u16,
+        end_y: u16,
+    ) -> Result<(), DriverError<SPI, RST, WR, PWR>> {
+        let [sx0, sx1] = start_x.to_be_bytes();
+        let [ex0, ex1] = end_x.to_be_bytes();
+        self.write_command(Cmd::CASET.bits(), &[sx0, sx1, ex0, ex1])
+            .await?;
+        let [sy0, sy1] = start_y.to_be_bytes();
+        let [ey0, ey1] = end_y.to_be_bytes();
+        self.write_command(Cmd::RASET.bits(), &[sy0, sy1, ey0, ey1])
+            .await
+    }
+    /// Sends the content of any [`Framebuffer`] to the display in a single `RAMWR` burst.
+    pub async fn flush(
+        &mut self,
+        framebuffer: &impl Framebuffer,
+    ) -> Result<(), DriverError<SPI, RST, WR, PWR>> {
+        let (start_x, start_y, end_x, end_y) = framebuffer.area();
+        self.set_frame_area(start_x, start_y, end_x, end_y).await?;
+        self.write_command(Cmd::RAMWR.bits(), framebuffer.bytes())
+            .await
+    }
+    /// Sends a [`Framebuffer`] in chunks of `chunk_len` bytes, yielding to the executor after
+    /// each chunk.
+    ///
+    /// Some HALs complete a large "async" write synchronously, which keeps a cooperative
+    /// executor from running other tasks for a whole frame upload. Yielding between chunks
+    /// bounds that latency to roughly one chunk's transfer time (a 4800-byte chunk, ten rows,
+    /// takes about 1 ms at 40 MHz). Smaller chunks keep other tasks more responsive but add a
+    /// DMA setup and a trip through the executor per chunk, lowering throughput. A `chunk_len`
+    /// of zero is treated as one byte.
+    pub async fn flush_yielding(
+        &mut self,
+        framebuffer: &impl Framebuffer,
+        chunk_len: usize,
+    ) -> Result<(), DriverError<SPI, RST, WR, PWR>> {
+        let (start_x, start_y, end_x, end_y) = framebuffer.area();
+        self.set_frame_area(start_x, start_y, end_x, end_y).await?;
+        self.write_command(Cmd::RAMWR.bits(), &[]).await?;
+        self.wr.set_high().map_err(Error::Pin)?;
+        for chunk in framebuffer.bytes().chunks(chunk_len.max(1)) {
+            self.spi.write(chunk).await.map_err(Error::Spi)?;
+            YieldNow(false).await;
+        }
+        self.spi.flush().await.map_err(Error::Spi)
+    }
+}
+
+/// Future that returns `Pending` once, waking itself, so the executor can run other tasks
+struct YieldNow(bool);
+
+impl Future for YieldNow {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        if self.0 {
+            return Poll::Ready(());
+        }
+        self.0 = true;
+        cx.waker().wake_by_ref();
+        Poll::Pending
+    }
+}
@@ -0,0 +1,289 @@
+//! Terminal-style text output on a framebuffer
+use core::fmt;
+
+use display_interface::DisplayError;
+use embedded_graphics::{
+    mono_font::MonoTextStyle,
+    pixelcolor::Rgb565,
+    prelude::*,
+    primitives::Rectangle,
+    text::{Baseline, Text},
+};
+
+use super::graphics::Display2in14;
+use super::st7789v::FRAME_SIZE;
+
+/// Serial-console-like text output on a [`Display2in14`].
+///
+/// Characters are drawn in a fixed grid derived from the font of `style`, starting at the
+/// top-left corner. The cursor advances after each character, wraps at the right edge, and
+/// when it moves past the last row the buffer scrolls up by one text line. `\n` starts a new
+/// line and `\r` returns to its start.
+///
+/// Implements [`core::fmt::Write`], so `writeln!(console, "t = {}", t)` works.
+pub struct TextConsole<'a, const N: usize = FRAME_SIZE> {
+    display: &'a mut Display2in14<N>,
+    style: MonoTextStyle<'a, Rgb565>,
+    background: Rgb565,
+    /// Cursor column, in characters
+    column: u32,
+    /// Cursor row, in text lines
+    row: u32,
+}
+
+impl<'a, const N: usize> TextConsole<'a, N> {
+    /// Create a console drawing into `display` with the cursor at the top-left corner.
+    ///
+    /// Glyph cells are painted with `background`, which is also used for lines scrolled in at
+    /// the bottom.
+    pub fn new(
+        display: &'a mut Display2in14<N>,
+        mut style: MonoTextStyle<'a, Rgb565>,
+        background: Rgb565,
+    ) -> Self {
+        style.background_color = Some(background);
+        Self {
+            display,
+            style,
+            background,
+            column: 0,
+            row: 0,
+        }
+    }
+    /// Number of characters that fit on one line
+    pub fn columns(&self) -> u32 {
+        let font = self.style.font;
+        let advance = font.character_size.width + font.character_spacing;
+        (self.display.size().width + font.character_spacing) / advance
+    }
+    /// Number of text lines that fit on the display
+    pub fn rows(&self) -> u32 {
+        self.display.size().height / self.style.font.character_size.height
+    }
+    /// Cursor position as `(column, row)` in characters
+    pub fn cursor(&self) -> (u32, u32) {
+        (self.column, self.row)
+    }
+    /// Move the cursor, clamped to the character grid
+    pub fn set_cursor(&mut self, column: u32, row: u32) {
+        self.column = column.min(self.columns().saturating_sub(1));
+        self.row = row.min(self.rows().saturating_sub(1));
+    }
+    /// Clear the display to the background color and move the cursor home
+    pub fn clear(&mut self) {
+        self.display.clear_buffer(self.background);
+        self.column = 0;
+        self.row = 0;
+    }
+    /// Write `text` at the cursor, wrapping and scrolling as needed
+    pub fn write_str(&mut self, text: &str) -> Result<(), DisplayError> {
+        if self.columns() == 0 || self.rows() == 0 {
+            return Ok(());
+        }
+        for c in text.chars() {
+            match c {
+                '\n' => self.new_line(),
+                '\r' => self.column = 0,
+                c => self.write_char(c)?,
+            }
+        }
+        Ok(())
+    }
+    fn write_char(&mut self, c: char) -> Result<(), DisplayError> {
+        if self.column >= self.columns() {
+            self.new_line();
+        }
+        let font = self.style.font;
+        let position = Point::new(
+            (self.column * (font.character_size.width + font.character_spacing)) as i32,
+            (self.row * font.character_size.height) as i32,
+        );
+        let mut utf8 = [0u8; 4];
+        Text::with_baseline(
+            c.encode_utf8(&mut utf8),
+            position,
+            self.style,
+            Baseline::Top,
+        )
+        .draw(self.display)?;
+        self.column += 1;
+        Ok(())
+    }
+    fn new_line(&mut self) {
+        self.column = 0;
+        if self.row + 1 < self.rows() {
+            self.row += 1;
+        } else {
+            self.display
+                .scroll_up(self.style.font.character_size.height, self.background);
+        }
+    }
+}
+
+impl<const N: usize> fmt::Write for TextConsole<'_, N> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        TextConsole::write_str(self, s).map_err(|_| fmt::Error)
+    }
+}
+
+/// A rectangular text region of a [`Display2in14`] with its own style and cursor.
+///
+/// Text written through [`core::fmt::Write`] is rendered with the area's `MonoTextStyle`,
+/// starting at the cursor. `\n` moves to the start of the next line; anything past the right
+/// edge or below the bottom of the area is clipped rather than wrapped or scrolled.
+pub struct TextArea<'a, const N: usize = FRAME_SIZE> {
+    display: &'a mut Display2in14<N>,
+    bounds: Rectangle,
+    style: MonoTextStyle<'a, Rgb565>,
+    /// Top-left corner of the next character, relative to `bounds`
+    cursor: Point,
+}
+
+impl<'a, const N: usize> TextArea<'a, N> {
+    /// Create a text area covering `bounds` of `display`, with the cursor at its top-left corner
+    pub fn new(
+        display: &'a mut Display2in14<N>,
+        bounds: Rectangle,
+        style: MonoTextStyle<'a, Rgb565>,
+    ) -> Self {
+        Self {
+            display,
+            bounds,
+            style,
+            cursor: Point::zero(),
+        }
+    }
+    /// Style used for text written from now on
+    pub fn style(&self) -> MonoTextStyle<'a, Rgb565> {
+        self.style
+    }
+    /// Change the font or colors used for text written from now on
+    pub fn set_style(&mut self, style: MonoTextStyle<'a, Rgb565>) {
+        self.style = style
+    }
+    /// Cursor position in pixels, relative to the top-left corner of the area
+    pub fn cursor(&self) -> Point {
+        self.cursor
+    }
+    /// Move the cursor, in pixels relative to the top-left corner of the area
+    pub fn set_cursor(&mut self, cursor: Point) {
+        self.cursor = cursor
+    }
+    /// Fill the area with `color` and move the cursor home
+    pub fn clear(&mut self, color: Rgb565) {
+        let _ = self.display.fill_solid(&self.bounds, color);
+        self.cursor = Point::zero();
+    }
+    /// Write `text` at the cursor, clipped to the area
+    pub fn write_str(&mut self, text: &str) -> Result<(), DisplayError> {
+        let line_height = self.style.font.character_size.height as i32;
+        for (i, line) in text.split('\n').enumerate() {
+            if i > 0 {
+                self.cursor = Point::new(0, self.cursor.y + line_height);
+            }
+            if line.is_empty() || self.cursor.y >= self.bounds.size.height as i32 {
+                continue;
+            }
+            let next = Text::with_baseline(
+                line,
+                self.bounds.top_left + self.cursor,
+                self.style,
+                Baseline::Top,
+            )
+            .draw(&mut self.display.clipped(&self.bounds))?;
+            self.cursor.x = next.x - self.bounds.top_left.x;
+        }
+        Ok(())
+    }
+}
+
+impl<const N: usize> fmt::Write for TextArea<'_, N> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        TextArea::write_str(self, s).map_err(|_| fmt::Error)
+    }
+}
+
+#[cfg(all(test, feature = "stack_alloc"))]
+mod tests {
+    use super::*;
+    use crate::st7789v::LINE_SIZE;
+    use core::fmt::Write;
+    use embedded_graphics::mono_font::ascii::FONT_6X10;
+
+    /// A 240 x 20 strip: 40 columns and two lines of `FONT_6X10`
+    type Strip = Display2in14<{ LINE_SIZE * 20 }>;
+
+    /// Pixels of the character cell at `(column, row)`
+    fn cell(display: &Strip, column: u16, row: u16) -> [[Option<Rgb565>; 6]; 10] {
+        core::array::from_fn(|y| {
+            core::array::from_fn(|x| display.pixel(column * 6 + x as u16, row * 10 + y as u16))
+        })
+    }
+
+    #[test]
+    fn console_advances_wraps_and_returns() {
+        let mut display = Strip::new_strip(Rgb565::BLACK);
+        let style = MonoTextStyle::new(&FONT_6X10, Rgb565::WHITE);
+        let mut console = TextConsole::new(&mut display, style, Rgb565::BLUE);
+        assert_eq!((console.columns(), console.rows()), (40, 2));
+
+        write!(console, "ab").unwrap();
+        assert_eq!(console.cursor(), (2, 0));
+        write!(console, "\rc").unwrap();
+        assert_eq!(console.cursor(), (1, 0));
+        writeln!(console).unwrap();
+        assert_eq!(console.cursor(), (0, 1));
+
+        console.set_cursor(39, 0);
+        write!(console, "xy").unwrap();
+        // The second character did not fit on line 0 and went to the start of line 1
+        assert_eq!(console.cursor(), (1, 1));
+        assert_eq!(display.pixel(0, 10), Some(Rgb565::BLUE));
+        assert_ne!(cell(&display, 0, 1), cell(&display, 1, 1));
+    }
+
+    #[test]
+    fn console_scrolls_on_the_last_line() {
+        let mut display = Strip::new_strip(Rgb565::BLACK);
+        let style = MonoTextStyle::new(&FONT_6X10, Rgb565::WHITE);
+        let mut console = TextConsole::new(&mut display, style, Rgb565::BLUE);
+        write!(console, "A\nB").unwrap();
+        let b = cell(&display, 0, 1);
+        assert_ne!(cell(&display, 0, 0), b);
+
+        let mut console = TextConsole::new(&mut display, style, Rgb565::BLUE);
+        console.set_cursor(1, 1);
+        writeln!(console).unwrap();
+        assert_eq!(console.cursor(), (0, 1));
+        // Line 1 moved up and a blank background line was scrolled in below it
+        assert_eq!(cell(&display, 0, 0), b);
+        assert!((0..240).all(|x| (10..20).all(|y| display.pixel(x, y) == Some(Rgb565::BLUE))));
+    }
+
+    #[test]
+    fn text_area_advances_lines_and_clips_to_its_bounds() {
+        let mut display = Strip::new_strip(Rgb565::BLACK);
+        let bounds = Rectangle::new(Point::new(10, 2), Size::new(20, 15));
+        let style = MonoTextStyle::new(&FONT_6X10, Rgb565::WHITE);
+        let mut area = TextArea::new(&mut display, bounds, style);
+        writeln!(area, "a").unwrap();
+        assert_eq!(area.cursor(), Point::new(0, 10));
+        area.set_cursor(Point::zero());
+        write!(area, "WWWWWW\nWWWWWW\nWWWWWW").unwrap();
+        assert_eq!(area.cursor().y, 20);
+        for y in 0..20 {
+            for x in 0..240 {
+                if !bounds.contains(Point::new(x as i32, y as i32)) {
+                    assert_eq!(display.pixel(x, y), Some(Rgb565::BLACK), "({x}, {y})");
+                }
+            }
+        }
+        // Both the first and the clipped second line were drawn inside the area
+        let lit = |rows: core::ops::Range<u16>| {
+            rows.flat_map(|y| (10..30).map(move |x| (x, y)))
+                .any(|(x, y)| display.pixel(x, y) == Some(Rgb565::WHITE))
+        };
+        assert!(lit(2..12));
+        assert!(lit(12..17));
+    }
+}
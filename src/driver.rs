@@ -1,54 +1,623 @@
-use super::interface::DisplayInterface;
+use super::error::{DriverError, Error};
+#[cfg(feature = "stats")]
+pub use super::interface::FlushStats;
+#[cfg(feature = "trace")]
+pub use super::interface::TraceEvent;
+use super::interface::{
+    Backlight, DiBus, DisplayInterface, LCD_ON_DELAY_US, LcdBus, NoDcPin, ResetPin, ResetTiming,
+};
 use super::st7789v::*;
-use display_interface::DisplayError;
-use embedded_hal::spi::SpiBus;
-use embedded_hal::{delay::DelayNs, digital::OutputPin};
+pub use super::st7789v::{Madctl, SelfDiagnostic};
+use core::ops::{Deref, DerefMut, Range};
+use display_interface::WriteOnlyDataCommand;
+use embedded_graphics::{pixelcolor::Rgb565, prelude::*};
+use embedded_hal::{
+    delay::DelayNs,
+    digital::{self, InputPin},
+};
 
 use super::graphics::*;
 
+#[cfg(feature = "heap_alloc")]
+extern crate alloc;
+#[cfg(feature = "heap_alloc")]
+use alloc::vec::Vec;
+
+/// Order of the color components as stored by the panel
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ColorOrder {
+    /// Red, green, blue
+    Rgb,
+    /// Blue, green, red
+    Bgr,
+}
+
+/// `COLMOD` value for 16 bits per pixel (RGB565), used by [`Lcd::init`]
+pub const COLMOD_RGB565: u8 = 0x65;
+/// `COLMOD` value for 12 bits per pixel (RGB444), two pixels packed in three bytes.
+///
+/// Select it with [`PixelFormat::Rgb444`] before flushing a
+/// [`Display2in14Rgb444`](crate::graphics::Display2in14Rgb444).
+pub const COLMOD_RGB444: u8 = 0x53;
+/// `COLMOD` value for 18 bits per pixel (RGB666), sent as three bytes per pixel
+pub const COLMOD_RGB666: u8 = 0x66;
+
+/// Power-on default porch setting (`PORCTRL`, 0xB2)
+pub const DEFAULT_PORCH: [u8; 5] = [0x0C, 0x0C, 0x00, 0x33, 0x33];
+
+/// Known-good power control 1 parameters (`PWCTRL1`, 0xD0): AVDD 6.8 V, AVCL -4.8 V, VDS 2.3 V
+pub const DEFAULT_PWCTRL1: [u8; 2] = [0xA4, 0xA1];
+/// Known-good VCOM setting (`VCOMS`, 0xBB): 1.35 V
+pub const DEFAULT_VCOMS: u8 = 0x32;
+/// Known-good VRH setting (`VRHS`, 0xC3): 4.35 V
+pub const DEFAULT_VRHS: u8 = 0x10;
+/// Known-good VDV setting (`VDVS`, 0xC4): 0 V
+pub const DEFAULT_VDVS: u8 = 0x20;
+
+/// Wait after `SLPOUT` (0x11), in milliseconds.
+///
+/// The datasheet accepts the next command after 5 ms, but the supply voltages and the
+/// registers loaded from NVM only settle within 120 ms, and `SLPIN` must not follow earlier.
+pub const SLPOUT_DELAY_MS: u32 = 120;
+/// Wait after `INVON` (0x21) or `INVOFF` (0x20), in milliseconds; the datasheet specifies none
+pub const INVON_DELAY_MS: u32 = 0;
+/// Wait after `DISPON` (0x29), in milliseconds; the datasheet specifies none
+pub const DISPON_DELAY_MS: u32 = 0;
+
+/// Number of pixels [`Lcd::write_colors`] buffers on the stack per SPI write
+pub const COLOR_CHUNK_PIXELS: usize = 64;
+
+/// One step of an initialization script run by [`Lcd::run_init_script`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct InitStep<'a> {
+    /// Command byte
+    pub cmd: u8,
+    /// Parameter bytes sent after the command, possibly empty
+    pub data: &'a [u8],
+    /// Delay after the step, in milliseconds
+    pub delay_ms: u32,
+}
+
+/// Register sequence sent by [`Lcd::init`] after the hardware reset.
+///
+/// It ends with `DISPON`. Copy and tweak it for panel revisions that need different settings,
+/// then run it with [`Lcd::run_init_script`].
+pub const DEFAULT_INIT: &[InitStep<'static>] = &[
+    InitStep {
+        cmd: Cmd::SLPOUT.bits(),
+        data: &[],
+        delay_ms: SLPOUT_DELAY_MS,
+    },
+    InitStep {
+        cmd: Cmd::COLMOD.bits(),
+        data: &[COLMOD_RGB565],
+        delay_ms: 0,
+    },
+    InitStep {
+        cmd: Cmd::VCOMS.bits(),
+        data: &[DEFAULT_VCOMS],
+        delay_ms: 0,
+    },
+    InitStep {
+        cmd: Cmd::VDVVRHEN.bits(),
+        data: &[0x01, 0xFF],
+        delay_ms: 0,
+    },
+    InitStep {
+        cmd: Cmd::VRHS.bits(),
+        data: &[DEFAULT_VRHS],
+        delay_ms: 0,
+    },
+    InitStep {
+        cmd: Cmd::VDVS.bits(),
+        data: &[DEFAULT_VDVS],
+        delay_ms: 0,
+    },
+    InitStep {
+        cmd: Cmd::PWCTRL1.bits(),
+        data: &DEFAULT_PWCTRL1,
+        delay_ms: 0,
+    },
+    InitStep {
+        cmd: Cmd::INVON.bits(),
+        data: &[],
+        delay_ms: INVON_DELAY_MS,
+    },
+    InitStep {
+        cmd: Cmd::DISPON.bits(),
+        data: &[],
+        delay_ms: DISPON_DELAY_MS,
+    },
+];
+
+/// Minimal bring-up for ST7789 modules without tuned power settings: sleep out, RGB565,
+/// inversion on, display on. Used by [`GenericSt7789`].
+pub const GENERIC_ST7789_INIT: &[InitStep<'static>] = &[
+    InitStep {
+        cmd: Cmd::SLPOUT.bits(),
+        data: &[],
+        delay_ms: SLPOUT_DELAY_MS,
+    },
+    InitStep {
+        cmd: Cmd::COLMOD.bits(),
+        data: &[COLMOD_RGB565],
+        delay_ms: 0,
+    },
+    InitStep {
+        cmd: Cmd::INVON.bits(),
+        data: &[],
+        delay_ms: INVON_DELAY_MS,
+    },
+    InitStep {
+        cmd: Cmd::DISPON.bits(),
+        data: &[],
+        delay_ms: DISPON_DELAY_MS,
+    },
+];
+
+/// Bring-up sequence and default register values for one panel variant, passed to
+/// [`Lcd::init_with_profile`].
+///
+/// Implement it for boards whose module needs a different register set instead of adding
+/// flags to [`InitOptions`].
+pub trait PanelProfile {
+    /// Commands sent after the reset. If the last one is `DISPON`, it is held back until the
+    /// optional background has been written.
+    fn init_steps(&self) -> &[InitStep<'_>];
+    /// MADCTL value written once the display is on
+    fn madctl(&self) -> Madctl {
+        Madctl::empty()
+    }
+    /// Pixel format selected after the init steps, if they leave a different one
+    fn pixel_format(&self) -> PixelFormat {
+        PixelFormat::Rgb565
+    }
+}
+
+/// The ATK-MD0240 module, initialized with [`DEFAULT_INIT`]; used by [`Lcd::init`].
+#[derive(Clone, Copy, Debug, Default)]
+pub struct AtkMd0240;
+
+impl PanelProfile for AtkMd0240 {
+    fn init_steps(&self) -> &[InitStep<'_>] {
+        DEFAULT_INIT
+    }
+}
+
+/// Any ST7789 module with power-on register defaults, initialized with
+/// [`GENERIC_ST7789_INIT`].
+#[derive(Clone, Copy, Debug, Default)]
+pub struct GenericSt7789;
+
+impl PanelProfile for GenericSt7789 {
+    fn init_steps(&self) -> &[InitStep<'_>] {
+        GENERIC_ST7789_INIT
+    }
+}
+
+/// Options for [`Lcd::init_with_options`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct InitOptions {
+    /// Raw RGB565 color to clear the panel to before it is switched on, if any
+    pub background: Option<u16>,
+    /// Timing of the hardware reset pulse
+    pub reset_timing: ResetTiming,
+    /// Send `INVON` (`true`, the default) or `INVOFF` during bring-up.
+    ///
+    /// Most ST7789V modules need inversion on to show correct colors; if yours shows a
+    /// negative image after [`Lcd::init`], set this to `false`.
+    pub inversion: bool,
+    /// Waits after the commands of [`DEFAULT_INIT`]
+    pub timing: InitTiming,
+    /// Keep the backlight off until the first frame is flushed; see
+    /// [`Lcd::backlight_on_after_first_flush`]
+    pub defer_backlight: bool,
+}
+
+/// Waits [`Lcd::init_with_options`] inserts after individual commands, in milliseconds.
+///
+/// The defaults are the datasheet values ([`SLPOUT_DELAY_MS`], [`INVON_DELAY_MS`],
+/// [`DISPON_DELAY_MS`]). Lengthen them for modules that misbehave when commands follow each
+/// other quickly.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct InitTiming {
+    /// Wait after `SLPOUT`
+    pub sleep_out_ms: u32,
+    /// Wait after `INVON` or `INVOFF`
+    pub inversion_ms: u32,
+    /// Wait after `DISPON`
+    pub display_on_ms: u32,
+}
+
+impl Default for InitTiming {
+    fn default() -> Self {
+        Self {
+            sleep_out_ms: SLPOUT_DELAY_MS,
+            inversion_ms: INVON_DELAY_MS,
+            display_on_ms: DISPON_DELAY_MS,
+        }
+    }
+}
+
+impl InitTiming {
+    /// Returns `step` with its delay replaced when it is one of the timed commands
+    fn apply<'a>(&self, mut step: InitStep<'a>) -> InitStep<'a> {
+        let cmd = step.cmd;
+        if cmd == Cmd::SLPOUT.bits() {
+            step.delay_ms = self.sleep_out_ms;
+        } else if cmd == Cmd::INVON.bits() || cmd == Cmd::INVOFF.bits() {
+            step.delay_ms = self.inversion_ms;
+        } else if cmd == Cmd::DISPON.bits() {
+            step.delay_ms = self.display_on_ms;
+        }
+        step
+    }
+}
+
+impl InitOptions {
+    /// Returns these options with the reset and command waits of `timings`.
+    ///
+    /// The backlight settle time is not part of the options; apply it after initialization
+    /// with [`Lcd::set_timings`].
+    pub fn with_timings(self, timings: Timings) -> Self {
+        Self {
+            reset_timing: timings.reset,
+            timing: timings.commands,
+            ..self
+        }
+    }
+}
+
+/// Every delay the driver inserts, in one place.
+///
+/// The waits do not depend on the SPI clock, so the defaults suit fast and slow buses alike;
+/// lengthen them when a module needs extra settle time, e.g. at the end of a long ribbon
+/// cable. [`Timings::default`] matches the values used when nothing is configured.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Timings {
+    /// Reset pulse, and the wait after a hardware or software reset
+    pub reset: ResetTiming,
+    /// Waits after `SLPOUT`, `INVON`/`INVOFF` and `DISPON`
+    pub commands: InitTiming,
+    /// Wait after switching the backlight on or off, in microseconds
+    pub backlight_us: u32,
+}
+
+impl Default for Timings {
+    fn default() -> Self {
+        Self {
+            reset: ResetTiming::default(),
+            commands: InitTiming::default(),
+            backlight_us: LCD_ON_DELAY_US,
+        }
+    }
+}
+
+impl Default for InitOptions {
+    fn default() -> Self {
+        Self {
+            background: None,
+            reset_timing: ResetTiming::default(),
+            inversion: true,
+            timing: InitTiming::default(),
+            defer_backlight: false,
+        }
+    }
+}
+
+/// Normal mode frame rate (`FRCTRL2`, 0xC6).
+///
+/// The rates assume the default porch setting ([`DEFAULT_PORCH`]) and dot inversion. Each
+/// variant maps to the `RTNA` divider value from the datasheet's frame rate table.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+#[repr(u8)]
+pub enum FrameRate {
+    Hz119 = 0x00,
+    Hz111 = 0x01,
+    Hz99 = 0x03,
+    Hz90 = 0x05,
+    Hz75 = 0x09,
+    /// Power-on default
+    #[default]
+    Hz60 = 0x0F,
+    Hz50 = 0x15,
+    Hz45 = 0x19,
+    Hz40 = 0x1E,
+    Hz39 = 0x1F,
+}
+
 pub struct Lcd<SPI, RST, WR, PWR> {
     interface: DisplayInterface<SPI, RST, WR, PWR>,
+    /// Last value written to the MADCTL register
+    madctl: u8,
+    /// Native geometry the controller is addressed with
+    geometry: Geometry,
+    /// Byte order colors are serialized in
+    byte_order: ByteOrder,
+    /// Last pixel format written to COLMOD, `None` if unknown
+    pixel_format: Option<PixelFormat>,
+    /// Vertical scroll area as `(top fixed lines, scrolling lines)`
+    scroll_area: (u16, u16),
+    /// Current scroll offset within the scroll area
+    scroll_offset: u16,
+    /// Waits after `SLPOUT`, `INVON`/`INVOFF` and `DISPON`
+    command_timing: InitTiming,
+    /// Switch the backlight on after the next complete flush
+    backlight_pending: bool,
+    /// Whether inversion is on, as last sent with `INVON` or `INVOFF` (off after reset)
+    inversion: bool,
 }
 
 impl<SPI, RST, WR, PWR> Lcd<SPI, RST, WR, PWR>
 where
-    SPI: SpiBus,
-    RST: OutputPin,
-    WR: OutputPin,
-    PWR: OutputPin,
+    SPI: LcdBus<WR>,
+    RST: ResetPin,
+    WR: digital::ErrorType,
+    PWR: Backlight,
 {
     pub fn init(spi: SPI, rst: RST, wr: WR, pwr: PWR, delay: &mut impl DelayNs) -> Self {
-        let interface = DisplayInterface::new(spi, rst, wr, pwr);
-        let mut lcd = Self { interface };
+        Self::init_with_background(spi, rst, wr, pwr, delay, None)
+    }
+    /// Initializes the display like [`init`](Self::init), optionally clearing it first.
+    ///
+    /// GRAM holds random data after power-up. With `Some(color)` the whole panel is filled with
+    /// the raw RGB565 `color` (see [`fill_frame`](Self::fill_frame)) before the display is
+    /// switched on, so no noise is shown. Pass `None` to skip the extra full-frame write when a
+    /// frame is flushed right after initialization anyway.
+    pub fn init_with_background(
+        spi: SPI,
+        rst: RST,
+        wr: WR,
+        pwr: PWR,
+        delay: &mut impl DelayNs,
+        background: Option<u16>,
+    ) -> Self {
+        let options = InitOptions {
+            background,
+            ..InitOptions::default()
+        };
+        Self::init_with_options(spi, rst, wr, pwr, delay, options)
+    }
+    /// Initializes the display with the given [`InitOptions`].
+    pub fn init_with_options(
+        spi: SPI,
+        rst: RST,
+        wr: WR,
+        pwr: PWR,
+        delay: &mut impl DelayNs,
+        options: InitOptions,
+    ) -> Self {
+        Self::init_with_profile(spi, rst, wr, pwr, delay, &AtkMd0240, options)
+    }
+    /// Initializes a panel variant described by `profile`, with the given [`InitOptions`].
+    ///
+    /// The profile's steps replace [`DEFAULT_INIT`]; the options still apply to them (inversion
+    /// and timing of the matching commands, background, reset timing). The background is
+    /// written as RGB565 after the profile's pixel format has been selected, so leave it at
+    /// `None` for profiles using another format.
+    pub fn init_with_profile(
+        spi: SPI,
+        rst: RST,
+        wr: WR,
+        pwr: PWR,
+        delay: &mut impl DelayNs,
+        profile: &impl PanelProfile,
+        options: InitOptions,
+    ) -> Self {
+        let mut interface = DisplayInterface::new(spi, rst, wr, pwr);
+        interface.set_reset_timing(options.reset_timing);
+        let mut lcd = Self {
+            interface,
+            madctl: 0x00,
+            geometry: Geometry::PORTRAIT,
+            byte_order: ByteOrder::default(),
+            pixel_format: None,
+            scroll_area: (0, ROWS),
+            scroll_offset: 0,
+            command_timing: options.timing,
+            backlight_pending: options.defer_backlight,
+            inversion: false,
+        };
         lcd.interface.reset(delay);
-        lcd.sleep_out(delay);
-        lcd.set_pixel_format(0x65);
-        lcd.display_inversion_on(delay);
-        lcd.display_on(delay);
-
-        lcd.mem_data_ac(0x00);
-        lcd.interface.lcd_on(delay);
-        
+        // Hold back a final DISPON so the panel can be cleared before it shows anything
+        let steps = profile.init_steps();
+        let (display_on, setup) = match steps.split_last() {
+            Some((last, setup)) if last.cmd == Cmd::DISPON.bits() => (Some(last), setup),
+            _ => (None, steps),
+        };
+        for step in setup {
+            let mut step = *step;
+            if step.cmd == Cmd::INVON.bits() && !options.inversion {
+                step.cmd = Cmd::INVOFF.bits();
+            }
+            let step = options.timing.apply(step);
+            lcd.run_init_script(core::slice::from_ref(&step), delay)
+                .unwrap();
+        }
+        if lcd.pixel_format != Some(profile.pixel_format()) {
+            lcd.set_pixel_format(profile.pixel_format());
+        }
+        if let Some(color) = options.background {
+            lcd.fill_frame(color).unwrap();
+        }
+        if let Some(display_on) = display_on {
+            let display_on = options.timing.apply(*display_on);
+            lcd.run_init_script(core::slice::from_ref(&display_on), delay)
+                .unwrap();
+        }
+
+        lcd.mem_data_ac(profile.madctl().bits());
+        if options.defer_backlight {
+            lcd.interface.lcd_off(delay);
+        } else {
+            lcd.interface.lcd_on(delay);
+        }
+
         lcd
     }
 
+    /// Pulses the reset pin with the given timing and waits for the controller to restart.
+    ///
+    /// The controller returns to its power-on state, so run an init script afterwards.
+    pub fn reset(
+        &mut self,
+        timing: ResetTiming,
+        delay: &mut impl DelayNs,
+    ) -> Result<(), DriverError<SPI, RST, WR, PWR>> {
+        self.interface.set_reset_timing(timing);
+        self.interface.try_reset(delay)
+    }
+    /// Resets the controller with `SWRESET` and restores the state this driver has set, for
+    /// recovering a panel that fell out of its commanded mode (after an ESD event, say).
+    ///
+    /// Use it when a periodic [`read_power_mode`](Self::read_power_mode) or
+    /// [`read_madctl`](Self::read_madctl) disagrees with what was commanded. Pass the
+    /// `profile` the panel was initialized with ([`AtkMd0240`] for [`init`](Self::init)); its
+    /// steps are sent again with the tracked inversion mode and [`timings`](Self::timings),
+    /// followed by the tracked MADCTL value, pixel format and scroll area and offset. The
+    /// backlight is left as it is.
+    ///
+    /// **Note:** A reset clears the frame memory, so the panel shows garbage until the
+    /// framebuffer is flushed again.
+    pub fn recover(
+        &mut self,
+        profile: &impl PanelProfile,
+        delay: &mut impl DelayNs,
+    ) -> Result<(), DriverError<SPI, RST, WR, PWR>> {
+        let pixel_format = self.pixel_format;
+        let inversion = self.inversion;
+        let (top_fixed, scroll_lines) = self.scroll_area;
+        let scroll_offset = self.scroll_offset;
+        self.interface.cmd(Cmd::SWRESET.bits())?;
+        delay.delay_ms(self.interface.reset_timing().wait_ms);
+        self.pixel_format = None;
+        self.inversion = false;
+        for step in profile.init_steps() {
+            let mut step = *step;
+            if step.cmd == Cmd::INVON.bits() && !inversion {
+                step.cmd = Cmd::INVOFF.bits();
+            }
+            let step = self.command_timing.apply(step);
+            self.run_init_script(core::slice::from_ref(&step), delay)?;
+        }
+        if self.inversion != inversion {
+            let cmd = if inversion { Cmd::INVON } else { Cmd::INVOFF };
+            self.interface.cmd(cmd.bits())?;
+            self.inversion = inversion;
+            delay.delay_ms(self.command_timing.inversion_ms);
+        }
+        self.interface
+            .write_command(Cmd::MADCTL.bits(), &[self.madctl])?;
+        if let Some(format) = pixel_format.filter(|&format| self.pixel_format != Some(format)) {
+            self.interface
+                .write_command(Cmd::COLMOD.bits(), &[format.colmod()])?;
+            self.pixel_format = Some(format);
+        }
+        if (top_fixed, scroll_lines) != (0, ROWS) {
+            self.set_scroll_area(top_fixed, scroll_lines, ROWS - top_fixed - scroll_lines)?;
+        }
+        if scroll_offset != 0 {
+            self.set_scroll_offset(scroll_offset)?;
+        }
+        Ok(())
+    }
+    /// Sends each step of `script` in order, waiting `delay_ms` after each one.
+    ///
+    /// `COLMOD` steps update the tracked [`pixel_format`](Self::pixel_format), and `INVON` and
+    /// `INVOFF` steps the inversion mode [`recover`](Self::recover) restores. Commands that
+    /// change MADCTL bypass the state tracked by [`Lcd`]; follow the script with
+    /// [`mem_data_ac`](Self::mem_data_ac) to keep them in sync.
+    pub fn run_init_script(
+        &mut self,
+        script: &[InitStep],
+        delay: &mut impl DelayNs,
+    ) -> Result<(), DriverError<SPI, RST, WR, PWR>> {
+        self.run_script(script, delay, false)
+    }
+    /// Sends `script` like [`run_init_script`](Self::run_init_script), but without flushing the
+    /// SPI bus after every command.
+    ///
+    /// Commands and parameters are queued with `cmd_async`/`data_async`. The bus is flushed
+    /// whenever DC has to change level, before a step's delay and once at the end, so queued
+    /// bytes are never latched with the wrong DC level. Runs of parameterless commands are sent
+    /// back to back without waiting, which speeds up bring-up on HALs whose `flush` is costly.
+    pub fn run_init_script_batched(
+        &mut self,
+        script: &[InitStep],
+        delay: &mut impl DelayNs,
+    ) -> Result<(), DriverError<SPI, RST, WR, PWR>> {
+        self.run_script(script, delay, true)
+    }
+    fn run_script(
+        &mut self,
+        script: &[InitStep],
+        delay: &mut impl DelayNs,
+        batched: bool,
+    ) -> Result<(), DriverError<SPI, RST, WR, PWR>> {
+        // Whether parameter bytes are still queued with DC high
+        let mut data_pending = false;
+        for step in script {
+            if batched {
+                if data_pending {
+                    self.interface.flush()?;
+                }
+                self.interface.cmd_async(step.cmd)?;
+                data_pending = !step.data.is_empty();
+                if data_pending {
+                    self.interface.flush()?;
+                    self.interface.data_async(step.data)?;
+                }
+            } else {
+                self.interface.write_command(step.cmd, step.data)?;
+            }
+            if step.cmd == Cmd::INVON.bits() || step.cmd == Cmd::INVOFF.bits() {
+                self.inversion = step.cmd == Cmd::INVON.bits();
+            }
+            if step.cmd == Cmd::COLMOD.bits() {
+                self.pixel_format = step
+                    .data
+                    .first()
+                    .copied()
+                    .and_then(PixelFormat::from_colmod);
+            }
+            if step.delay_ms > 0 {
+                if batched {
+                    self.interface.flush()?;
+                    data_pending = false;
+                }
+                delay.delay_ms(step.delay_ms);
+            }
+        }
+        if batched {
+            self.interface.flush()?;
+        }
+        Ok(())
+    }
     /// Turn off sleep mode
     #[inline]
     pub fn sleep_out(&mut self, delay: &mut impl DelayNs) {
         self.interface.cmd(Cmd::SLPOUT.bits()).unwrap();
-        delay.delay_ms(WAIT_MS);
+        delay.delay_ms(self.command_timing.sleep_out_ms);
     }
-    /// Recover from display inversion mode
+    /// Enter display inversion mode
     #[inline]
     pub fn display_inversion_on(&mut self, delay: &mut impl DelayNs) {
         self.interface.cmd(Cmd::INVON.bits()).unwrap();
-        delay.delay_ms(WAIT_MS);
+        self.inversion = true;
+        delay.delay_ms(self.command_timing.inversion_ms);
+    }
+    /// Leave display inversion mode
+    #[inline]
+    pub fn display_inversion_off(&mut self, delay: &mut impl DelayNs) {
+        self.interface.cmd(Cmd::INVOFF.bits()).unwrap();
+        self.inversion = false;
+        delay.delay_ms(self.command_timing.inversion_ms);
     }
     /// Recover from DISPLAY OFF mode
     #[inline]
     pub fn display_on(&mut self, delay: &mut impl DelayNs) {
         self.interface.cmd(Cmd::DISPON.bits()).unwrap();
-        delay.delay_ms(WAIT_MS);
+        delay.delay_ms(self.command_timing.display_on_ms);
     }
     /// Sets the Memory Data Access Control (MADCTL) register.
     ///
@@ -80,20 +649,145 @@ where
     /// This function will panic if sending the command or data fails.
     ///
     /// # Example
-    /// ```rust
+    /// ```rust,ignore
     /// // Set memory access control to normal mode, top-to-bottom, left-to-right, RGB order
     /// display.mem_data_ac(0b0000_0000);
+    /// // Mirror horizontally with BGR color order
+    /// display.mem_data_ac((Madctl::MX | Madctl::BGR).bits());
     /// ```
     #[inline]
     pub fn mem_data_ac(&mut self, param: u8) {
-        self.interface.cmd(Cmd::MADCTL.bits()).unwrap();
-        self.interface.data(&[param]).unwrap();
+        self.interface
+            .write_command(Cmd::MADCTL.bits(), &[param])
+            .unwrap();
+        self.madctl = param;
+    }
+    /// Mirrors the image left-right by toggling the MADCTL column address order (MX).
+    ///
+    /// Only the MX bit is changed; the rest of the current MADCTL value is preserved.
+    pub fn set_mirror_x(&mut self, mirror: bool) -> Result<(), DriverError<SPI, RST, WR, PWR>> {
+        self.update_madctl(Madctl::MX, mirror)
+    }
+    /// Mirrors the image top-bottom by toggling the MADCTL page address order (MY).
+    ///
+    /// Only the MY bit is changed; the rest of the current MADCTL value is preserved.
+    pub fn set_mirror_y(&mut self, mirror: bool) -> Result<(), DriverError<SPI, RST, WR, PWR>> {
+        self.update_madctl(Madctl::MY, mirror)
+    }
+    /// Selects BGR (`true`) or RGB (`false`) color order via the MADCTL RGB/BGR bit.
+    ///
+    /// Only the color order bit is changed; the rest of the current MADCTL value is preserved.
+    pub fn set_bgr(&mut self, bgr: bool) -> Result<(), DriverError<SPI, RST, WR, PWR>> {
+        self.update_madctl(Madctl::BGR, bgr)
+    }
+    /// Returns the native geometry the controller is addressed with.
+    pub fn geometry(&self) -> Geometry {
+        self.geometry
+    }
+    /// Selects portrait or landscape as the native addressing of the panel.
+    ///
+    /// Landscape geometries set the MADCTL row/column exchange bit (MV), so column addresses
+    /// run along the long edge and a landscape framebuffer can be flushed without software
+    /// rotation. Combine it with [`set_mirror_x`](Self::set_mirror_x) or
+    /// [`set_mirror_y`](Self::set_mirror_y) to pick which way the image faces.
+    pub fn set_geometry(
+        &mut self,
+        geometry: Geometry,
+    ) -> Result<(), DriverError<SPI, RST, WR, PWR>> {
+        self.update_madctl(Madctl::MV, geometry.is_landscape())?;
+        self.geometry = geometry;
+        Ok(())
+    }
+    /// Lets the controller apply `rotation` by setting the MADCTL scan direction, instead of
+    /// rotating pixels in software.
+    ///
+    /// The MV, MX and MY bits are replaced by [`DisplayRotation::madctl`] in a single MADCTL
+    /// write, the color order and refresh bits are kept, and the geometry becomes
+    /// [`DisplayRotation::geometry`]. Keep the framebuffer at `DisplayRotation::Rotate0` with
+    /// the same geometry: drawing then writes each pixel at its logical offset, and
+    /// [`flush`](Self::flush) remains a single burst with no transposition, even for
+    /// `Rotate90` and `Rotate270`.
+    ///
+    /// ```ignore
+    /// lcd.set_hardware_rotation(DisplayRotation::Rotate90)?;
+    /// display.set_geometry(DisplayRotation::Rotate90.geometry());
+    /// ```
+    pub fn set_hardware_rotation(
+        &mut self,
+        rotation: DisplayRotation,
+    ) -> Result<(), DriverError<SPI, RST, WR, PWR>> {
+        let scan = Madctl::MV | Madctl::MX | Madctl::MY;
+        let param = (self.madctl & !scan.bits()) | rotation.madctl().bits();
+        self.interface.write_command(Cmd::MADCTL.bits(), &[param])?;
+        self.madctl = param;
+        self.geometry = rotation.geometry();
+        Ok(())
+    }
+    /// Whether `(x, y)` lies on the panel in the current geometry
+    pub fn in_bounds(&self, x: u16, y: u16) -> bool {
+        x < self.geometry.width && y < self.geometry.height
+    }
+    /// Returns the byte order colors are serialized in.
+    pub fn byte_order(&self) -> ByteOrder {
+        self.byte_order
+    }
+    /// Selects whether `set_pixel`, [`WriteSession::push`] and the `DrawTarget` implementation
+    /// send each color MSB-first (the default) or LSB-first.
+    ///
+    /// Raw byte slices such as [`draw_image`](Self::draw_image) data and framebuffers are sent
+    /// unchanged; set the framebuffer's own byte order to match.
+    pub fn set_byte_order(&mut self, byte_order: ByteOrder) {
+        self.byte_order = byte_order
+    }
+    /// Sets or clears `mask` in the tracked MADCTL value and re-sends the register
+    fn update_madctl(
+        &mut self,
+        mask: Madctl,
+        enable: bool,
+    ) -> Result<(), DriverError<SPI, RST, WR, PWR>> {
+        let param = if enable {
+            self.madctl | mask.bits()
+        } else {
+            self.madctl & !mask.bits()
+        };
+        self.interface.write_command(Cmd::MADCTL.bits(), &[param])?;
+        self.madctl = param;
+        Ok(())
     }
     /// Sets the interface pixel format (COLMOD, 0x3A).
     ///
+    /// The format is remembered, and [`flush`](Self::flush) refuses framebuffers whose
+    /// [`Framebuffer::pixel_format`] does not match it.
+    ///
+    /// # Panics
+    /// This function will panic if sending the command or data fails.
+    pub fn set_pixel_format(&mut self, format: PixelFormat) {
+        self.set_pixel_format_raw(format.colmod());
+    }
+    /// Switches the interface pixel format to the layout of `framebuffer`, if it differs.
+    ///
+    /// The framebuffer type fixes the format ([`Display2in14`] is RGB565,
+    /// [`Display2in14Rgb444`] is RGB444), so switching formats means switching buffers; call
+    /// this with the new buffer before flushing it instead of picking a [`PixelFormat`] by
+    /// hand, and the controller and buffer cannot disagree.
+    pub fn set_pixel_format_for(
+        &mut self,
+        framebuffer: &impl Framebuffer,
+    ) -> Result<(), DriverError<SPI, RST, WR, PWR>> {
+        let format = framebuffer.pixel_format();
+        if self.pixel_format != Some(format) {
+            self.interface
+                .write_command(Cmd::COLMOD.bits(), &[format.colmod()])?;
+            self.pixel_format = Some(format);
+        }
+        Ok(())
+    }
+    /// Sets the interface pixel format (COLMOD, 0x3A) from a raw register value.
+    ///
     /// This function defines the format of RGB picture data to be transferred via the MCU interface.
     /// The pixel format determines the number of bits per pixel (bpp) and affects both the RGB
-    /// interface and the control interface.
+    /// interface and the control interface. Prefer [`set_pixel_format`](Self::set_pixel_format);
+    /// values that do not match a [`PixelFormat`] disable the format check in `flush`.
     ///
     /// # Parameters
     /// - `param`: An 8-bit value that specifies the pixel format.
@@ -103,7 +797,7 @@ where
     ///     - `110` (0b0110_0000): 262K colors  
     ///   - **D3**: Always set to `0`.
     ///   - **D2-D0**: Control interface color format  
-    ///     - `011` (0b0000_0110): 12-bit per pixel  
+    ///     - `011` (0b0000_0011): 12-bit per pixel  
     ///     - `101` (0b0000_0101): 16-bit per pixel  
     ///     - `110` (0b0000_0110): 18-bit per pixel  
     ///     - `111` (0b0000_0111): 16M truncated  
@@ -112,23 +806,287 @@ where
     /// This function will panic if sending the command or data fails.
     ///
     /// # Example
-    /// ```rust
+    /// ```rust,ignore
     /// // Set the display to 262K colors/16-bit per pixel mode
-    /// display.set_pixel_format(0b01100101);
+    /// display.set_pixel_format_raw(0b01100101);
     /// ```
-    pub fn set_pixel_format(&mut self, param: u8) {
-        self.interface.cmd(Cmd::COLMOD.bits()).unwrap();
-        self.interface.data(&[param]).unwrap();
+    pub fn set_pixel_format_raw(&mut self, param: u8) {
+        self.interface
+            .write_command(Cmd::COLMOD.bits(), &[param])
+            .unwrap();
+        self.pixel_format = PixelFormat::from_colmod(param);
+    }
+    /// Returns the pixel format last selected, or `None` if it was set to a raw value that
+    /// does not match a [`PixelFormat`].
+    pub fn pixel_format(&self) -> Option<PixelFormat> {
+        self.pixel_format
+    }
+    /// Sets the porch timing (PORCTRL, 0xB2).
+    ///
+    /// # Parameters
+    /// - `params`: The five raw parameter bytes.
+    ///   - `params[0]`: Back porch in normal mode (`BPA`, D6-D0)
+    ///   - `params[1]`: Front porch in normal mode (`FPA`, D6-D0)
+    ///   - `params[2]`: Separate porch control enable (`PSEN`, D0)
+    ///   - `params[3]`: Back porch (D7-D4) and front porch (D3-D0) in idle mode
+    ///   - `params[4]`: Back porch (D7-D4) and front porch (D3-D0) in partial mode
+    ///
+    /// [`DEFAULT_PORCH`] holds the power-on values.
+    pub fn set_porch(&mut self, params: [u8; 5]) -> Result<(), DriverError<SPI, RST, WR, PWR>> {
+        self.interface.write_command(Cmd::PORCTRL.bits(), &params)
+    }
+    /// Sets the VCOM voltage (VCOMS, 0xBB).
+    ///
+    /// # Parameters
+    /// - `param`: VCOM level in D5-D0, from 0.1 V (`0x00`) to 1.675 V (`0x3F`) in 25 mV steps.
+    ///   [`DEFAULT_VCOMS`] is a known-good value for this module.
+    pub fn set_vcom(&mut self, param: u8) -> Result<(), DriverError<SPI, RST, WR, PWR>> {
+        self.interface.write_command(Cmd::VCOMS.bits(), &[param])
+    }
+    /// Sets the VRH voltage (VRHS, 0xC3), enabling command control of VDV/VRH first.
+    ///
+    /// # Parameters
+    /// - `param`: VRH level in D5-D0, from 3.55 V (`0x00`) to 5.5 V (`0x27`).
+    ///   [`DEFAULT_VRHS`] is a known-good value for this module.
+    pub fn set_vrh(&mut self, param: u8) -> Result<(), DriverError<SPI, RST, WR, PWR>> {
+        self.enable_vdv_vrh()?;
+        self.interface.write_command(Cmd::VRHS.bits(), &[param])
+    }
+    /// Sets the VDV voltage (VDVS, 0xC4), enabling command control of VDV/VRH first.
+    ///
+    /// # Parameters
+    /// - `param`: VDV level in D5-D0, from -0.8 V (`0x00`) to 0.8 V (`0x3F`), `0x20` being 0 V.
+    ///   [`DEFAULT_VDVS`] is a known-good value for this module.
+    pub fn set_vdv(&mut self, param: u8) -> Result<(), DriverError<SPI, RST, WR, PWR>> {
+        self.enable_vdv_vrh()?;
+        self.interface.write_command(Cmd::VDVS.bits(), &[param])
+    }
+    /// Sets the power control 1 register (PWCTRL1, 0xD0).
+    ///
+    /// # Parameters
+    /// - `params[0]`: Always `0xA4`.
+    /// - `params[1]`: AVDD (D7-D6), AVCL (D5-D4) and VDS (D1-D0) levels.
+    ///
+    /// [`DEFAULT_PWCTRL1`] is a known-good value for this module.
+    pub fn set_power_control(
+        &mut self,
+        params: [u8; 2],
+    ) -> Result<(), DriverError<SPI, RST, WR, PWR>> {
+        self.interface.write_command(Cmd::PWCTRL1.bits(), &params)
+    }
+    /// Lets VDV and VRH be set by command instead of NVM (VDVVRHEN, 0xC2)
+    fn enable_vdv_vrh(&mut self) -> Result<(), DriverError<SPI, RST, WR, PWR>> {
+        self.interface
+            .write_command(Cmd::VDVVRHEN.bits(), &[0x01, 0xFF])
+    }
+    /// Sets the frame rate in normal mode (FRCTRL2, 0xC6).
+    ///
+    /// Lower rates reduce power consumption; changing the rate can also avoid beat patterns
+    /// with cameras or other periodic light sources.
+    pub fn set_frame_rate(
+        &mut self,
+        rate: FrameRate,
+    ) -> Result<(), DriverError<SPI, RST, WR, PWR>> {
+        self.interface
+            .write_command(Cmd::FRCTRL2.bits(), &[rate as u8])
+    }
+    /// Defines the vertical scrolling area (VSCRDEF, 0x33) and resets the scroll offset.
+    ///
+    /// The panel's 320 gate lines are split into a fixed band at the top, a scrolling band and
+    /// a fixed band at the bottom; the three must add up to 320, and the scrolling band must not
+    /// be empty. After a reset the whole panel scrolls.
+    pub fn set_scroll_area(
+        &mut self,
+        top_fixed: u16,
+        scroll_lines: u16,
+        bottom_fixed: u16,
+    ) -> Result<(), DriverError<SPI, RST, WR, PWR>> {
+        if scroll_lines == 0
+            || top_fixed as u32 + scroll_lines as u32 + bottom_fixed as u32 != ROWS as u32
+        {
+            return Err(Error::OutOfBounds);
+        }
+        let [t0, t1] = top_fixed.to_be_bytes();
+        let [s0, s1] = scroll_lines.to_be_bytes();
+        let [b0, b1] = bottom_fixed.to_be_bytes();
+        self.interface
+            .write_command(Cmd::VSCRDEF.bits(), &[t0, t1, s0, s1, b0, b1])?;
+        self.scroll_area = (top_fixed, scroll_lines);
+        self.set_scroll_offset(0)
+    }
+    /// Scrolls the scroll area so that its first visible line is GRAM row
+    /// `top_fixed + offset` (VSCRSADD, 0x37).
+    ///
+    /// `offset` wraps around the height of the scroll area.
+    pub fn set_scroll_offset(&mut self, offset: u16) -> Result<(), DriverError<SPI, RST, WR, PWR>> {
+        let (top_fixed, lines) = self.scroll_area;
+        let offset = offset % lines;
+        let [a0, a1] = (top_fixed + offset).to_be_bytes();
+        self.interface
+            .write_command(Cmd::VSCRSADD.bits(), &[a0, a1])?;
+        self.scroll_offset = offset;
+        Ok(())
+    }
+    /// Scrolls the content of the scroll area up by `lines` using the controller's vertical
+    /// scrolling, and returns the GRAM rows now shown at the bottom of the area.
+    ///
+    /// Nothing is redrawn: the lines that scrolled out at the top reappear at the bottom, so
+    /// the caller overwrites the returned rows (e.g. with [`fill_rect`](Self::fill_rect) and the
+    /// next line of text). The rows are two ranges because they wrap from the end of the scroll
+    /// area back to its start; the second range is empty unless `lines` crosses that point.
+    /// Iterate both with `.into_iter().flatten()`. `lines` is clamped to the height of the
+    /// area.
+    ///
+    /// Scrolling runs along the panel's gate lines, i.e. the rows of
+    /// [`Geometry::PORTRAIT`]; in landscape geometry the content moves sideways.
+    pub fn scroll_text_up(
+        &mut self,
+        lines: u16,
+    ) -> Result<[Range<u16>; 2], DriverError<SPI, RST, WR, PWR>> {
+        let (top_fixed, area_lines) = self.scroll_area;
+        let lines = lines.min(area_lines);
+        let start = self.scroll_offset;
+        self.set_scroll_offset(start + lines)?;
+        let end = start + lines;
+        Ok(if end <= area_lines {
+            [top_fixed + start..top_fixed + end, top_fixed..top_fixed]
+        } else {
+            [
+                top_fixed + start..top_fixed + area_lines,
+                top_fixed..top_fixed + end - area_lines,
+            ]
+        })
     }
 }
 
 impl<SPI, RST, WR, PWR> Lcd<SPI, RST, WR, PWR>
 where
-    SPI: SpiBus,
-    RST: OutputPin,
-    WR: OutputPin,
-    PWR: OutputPin,
+    SPI: LcdBus<WR>,
+    RST: ResetPin,
+    WR: digital::ErrorType,
+    PWR: Backlight,
 {
+    /// Returns the bus traffic counted since the last call and resets the counters.
+    ///
+    /// Call it before and after a flush to see how many bytes and SPI transactions the flush
+    /// incurred. Only available with the `stats` feature.
+    #[cfg(feature = "stats")]
+    pub fn take_stats(&mut self) -> FlushStats {
+        self.interface.take_stats()
+    }
+    /// Sets a function called with every command byte and data length before it goes out over
+    /// SPI, or removes it with `None`.
+    ///
+    /// Wire it to `defmt` or a UART to log the exact command stream while debugging bring-up.
+    /// Only available with the `trace` feature; without it the driver carries no hook at all.
+    #[cfg(feature = "trace")]
+    pub fn set_trace_hook(&mut self, hook: Option<fn(TraceEvent)>) {
+        self.interface.set_trace_hook(hook);
+    }
+    /// Limits how many bytes are passed to a single `SpiBus::write`, for HALs or DMA backends
+    /// that cap the length of one transfer.
+    ///
+    /// Longer writes, such as a full-frame [`flush`](Self::flush), are split transparently.
+    /// There is no limit by default, and a limit of zero is raised to one byte. The limit is a
+    /// runtime setting because it usually depends on how the HAL or DMA channel was configured.
+    pub fn set_max_transfer(&mut self, max_transfer: usize) {
+        self.interface.set_max_transfer(max_transfer);
+    }
+    /// Sets every delay the driver inserts at once: the reset wait of
+    /// [`recover`](Self::recover), the waits of [`sleep_out`](Self::sleep_out), the inversion
+    /// commands and [`display_on`](Self::display_on) (also applied to the script replayed by
+    /// `recover`), and the backlight settle time.
+    ///
+    /// [`reset`](Self::reset) still takes its own [`ResetTiming`] and keeps it for later.
+    pub fn set_timings(&mut self, timings: Timings) {
+        self.interface.set_reset_timing(timings.reset);
+        self.interface.set_lcd_on_delay(timings.backlight_us);
+        self.command_timing = timings.commands;
+    }
+    /// Returns the delays currently in use; see [`set_timings`](Self::set_timings).
+    pub fn timings(&self) -> Timings {
+        Timings {
+            reset: self.interface.reset_timing(),
+            commands: self.command_timing,
+            backlight_us: self.interface.lcd_on_delay(),
+        }
+    }
+    /// Switches the backlight fully on after the next complete flush instead of leaving it as
+    /// it is, so the uninitialized GRAM shown after power-up is never lit.
+    ///
+    /// The backlight is switched on only once the frame's last byte has left the bus, after
+    /// [`flush`](Self::flush) and the methods built on it, [`flush_with_progress`],
+    /// [`flush_rotated`] or [`flush_packed`]; partial uploads such as `FrameDiff` rows,
+    /// fills or [`begin_write`](Self::begin_write) do not count. It does not switch the
+    /// backlight off, so arm it before the backlight is first turned on, most simply with
+    /// [`InitOptions::defer_backlight`], which keeps it off during init. Passing `false` cancels
+    /// a pending switch.
+    ///
+    /// [`flush_with_progress`]: Self::flush_with_progress
+    /// [`flush_rotated`]: Self::flush_rotated
+    /// [`flush_packed`]: Self::flush_packed
+    pub fn backlight_on_after_first_flush(&mut self, enable: bool) {
+        self.backlight_pending = enable;
+    }
+    /// Sets the backlight brightness, from 0 (off) to 255 (full).
+    ///
+    /// With a [`PwmBacklight`](crate::interface::PwmBacklight) the PWM duty cycle follows
+    /// `level`; a plain GPIO backlight is switched on for any non-zero level.
+    pub fn set_backlight(&mut self, level: u8) -> Result<(), DriverError<SPI, RST, WR, PWR>> {
+        self.interface.set_backlight(level)
+    }
+    /// Ramps the backlight from `from` to `to` in `steps` equal steps spread over
+    /// `duration_ms`, for a fade-in on a splash screen or a soft power-off.
+    ///
+    /// Needs a [`PwmBacklight`](crate::interface::PwmBacklight); a plain GPIO backlight is
+    /// switched to `to` at once.
+    pub fn fade_backlight(
+        &mut self,
+        from: u8,
+        to: u8,
+        steps: u16,
+        duration_ms: u32,
+        delay: &mut impl DelayNs,
+    ) -> Result<(), DriverError<SPI, RST, WR, PWR>> {
+        self.interface
+            .fade_backlight(from, to, steps, duration_ms, delay)
+    }
+    /// Sets how long [`lcd_on`](DisplayInterface::lcd_on) and
+    /// [`lcd_off`](DisplayInterface::lcd_off) wait after switching the backlight, in
+    /// microseconds, for backlight drivers that need time to settle. The default is 1 µs.
+    pub fn set_lcd_on_delay(&mut self, delay_us: u32) {
+        self.interface.set_lcd_on_delay(delay_us);
+    }
+    /// Sends any command byte followed by `params`, for registers this driver does not wrap
+    /// (vendor commands, gamma tables and the like).
+    ///
+    /// Nothing is checked: a wrong command or parameter can misconfigure the panel until the
+    /// next reset. Commands that change MADCTL, COLMOD or the scroll area also bypass the state
+    /// tracked by [`Lcd`]; prefer the dedicated methods for those.
+    pub fn send_raw(
+        &mut self,
+        cmd: u8,
+        params: &[u8],
+    ) -> Result<(), DriverError<SPI, RST, WR, PWR>> {
+        self.interface.write_command(cmd, params)
+    }
+    /// Sends a `NOP` command.
+    ///
+    /// `NOP` has no effect on the controller's configuration, but it terminates an in-progress
+    /// memory write (`RAMWR`) without changing GRAM contents.
+    pub fn nop(&mut self) -> Result<(), DriverError<SPI, RST, WR, PWR>> {
+        self.interface.cmd(Cmd::NOP.bits())
+    }
+    /// Waits until the SPI bus is idle, then sends a `NOP`.
+    ///
+    /// Use this as a barrier between a burst of `*_async` writes and a subsequent command. The
+    /// pending bytes are flushed before DC is driven low, so none of them is latched as a
+    /// command; once it returns, any memory write has been terminated and all bytes have left
+    /// the bus.
+    pub fn sync(&mut self) -> Result<(), DriverError<SPI, RST, WR, PWR>> {
+        self.interface.flush()?;
+        self.interface.cmd(Cmd::NOP.bits())
+    }
     /// Sets the frame memory area (column and row address range).
     ///
     /// This function defines the rectangular area of the display where pixel data will be written.
@@ -143,38 +1101,280 @@ where
     ///
     /// # Returns
     /// - `Ok(())` if the command and data transmissions succeed.
-    /// - `Err(DisplayError)` if sending a command or data fails.
+    /// - `Err(Error::Pin)` or `Err(Error::Spi)` if sending a command or data fails.
     pub fn set_frame_area(
         &mut self,
         start_x: u16,
         start_y: u16,
         end_x: u16,
         end_y: u16,
-    ) -> Result<(), DisplayError> {
-        self.interface.cmd(Cmd::CASET.bits())?;
-        self.interface.data(&[
-            (start_x >> 8) as u8,
-            start_x as u8,
-            (end_x >> 8) as u8,
-            end_x as u8,
-        ])?;
-        self.interface.cmd(Cmd::RASET.bits())?;
-        self.interface.data(&[
-            (start_y >> 8) as u8,
-            start_y as u8,
-            (end_y >> 8) as u8,
-            end_y as u8,
-        ])
-    }
-    // pub fn set_cursor(&mut self, x: u16, y: u16) -> Result<(), DisplayError> {
+    ) -> Result<(), DriverError<SPI, RST, WR, PWR>> {
+        self.set_columns(start_x, end_x)?;
+        self.set_rows(start_y, end_y)
+    }
+    /// Sets only the column address range (`CASET`), leaving the row range untouched.
+    ///
+    /// Together with [`set_rows`](Self::set_rows) this lets a scanline renderer set the columns
+    /// once and only move the row window for each line.
+    pub fn set_columns(
+        &mut self,
+        start_x: u16,
+        end_x: u16,
+    ) -> Result<(), DriverError<SPI, RST, WR, PWR>> {
+        self.interface.write_command(
+            Cmd::CASET.bits(),
+            &[
+                (start_x >> 8) as u8,
+                start_x as u8,
+                (end_x >> 8) as u8,
+                end_x as u8,
+            ],
+        )
+    }
+    /// Sets only the row address range (`RASET`), leaving the column range untouched.
+    pub fn set_rows(
+        &mut self,
+        start_y: u16,
+        end_y: u16,
+    ) -> Result<(), DriverError<SPI, RST, WR, PWR>> {
+        self.interface.write_command(
+            Cmd::RASET.bits(),
+            &[
+                (start_y >> 8) as u8,
+                start_y as u8,
+                (end_y >> 8) as u8,
+                end_y as u8,
+            ],
+        )
+    }
+    // pub fn set_cursor(&mut self, x: u16, y: u16) -> Result<(), DriverError<SPI, RST, WR, PWR>> {
     //     self.set_frame_area(x, y, x, y)
     // }
-    pub fn set_pixel(&mut self, x: u16, y: u16, color: u16) -> Result<(), DisplayError> {
+    /// Writes one raw RGB565 pixel.
+    ///
+    /// Pixels outside the panel in the current geometry are ignored, as with
+    /// [`DrawTarget`], so off-screen coordinates never reach the controller.
+    pub fn set_pixel(
+        &mut self,
+        x: u16,
+        y: u16,
+        color: u16,
+    ) -> Result<(), DriverError<SPI, RST, WR, PWR>> {
+        if !self.in_bounds(x, y) {
+            return Ok(());
+        }
         self.set_frame_area(x, y, x, y)?;
+        self.interface
+            .write_command(Cmd::RAMWR.bits(), &self.byte_order.bytes(color))
+    }
+    /// Sets the frame area and starts a memory write (`RAMWR`) into it.
+    ///
+    /// Pixels are then streamed through the returned [`WriteSession`], which mutably borrows
+    /// the `Lcd` until it is dropped or [`end`](WriteSession::end)ed, so no other command can
+    /// interrupt the write by accident.
+    pub fn begin_write(
+        &mut self,
+        start_x: u16,
+        start_y: u16,
+        end_x: u16,
+        end_y: u16,
+    ) -> SessionResult<'_, SPI, RST, WR, PWR> {
+        self.set_frame_area(start_x, start_y, end_x, end_y)?;
         self.interface.cmd(Cmd::RAMWR.bits())?;
-        self.interface.data(&[(color >> 8) as u8, color as u8])
+        Ok(WriteSession {
+            lcd: self,
+            window: (start_x, start_y, end_x, end_y),
+            offset: 0,
+            row_break: None,
+            dc_high: false,
+            resume_pending: false,
+        })
+    }
+    /// Writes more pixel data into the current memory write without re-sending `RAMWR`.
+    ///
+    /// The controller keeps filling the window set by the last `RAMWR` (for example one
+    /// started with [`begin_write`](Self::begin_write)) as long as no other command is sent,
+    /// so a streaming decoder can push chunks as they arrive. The data must stay within that
+    /// window: writing past its last pixel wraps back to its top-left corner. Any command,
+    /// including the ones sent by `set_frame_area` or `nop`, ends the write.
+    pub fn append_data(&mut self, bytes: &[u8]) -> Result<(), DriverError<SPI, RST, WR, PWR>> {
+        self.interface.data(bytes)
+    }
+    /// Like [`append_data`](Self::append_data), but returns without waiting for the bytes to
+    /// leave the bus.
+    ///
+    /// Pushing a frame as many small slices with `append_data` waits for each one; with this
+    /// method a DMA-backed `SpiBus` can queue the next slice while the previous one is still
+    /// being clocked out. Call [`sync`](Self::sync) once at the end of the burst: it waits for
+    /// the queued bytes to leave the bus before driving DC low, so none is taken as a command.
+    pub fn append_data_nowait(
+        &mut self,
+        bytes: &[u8],
+    ) -> Result<(), DriverError<SPI, RST, WR, PWR>> {
+        self.interface.data_nowait(bytes)
     }
-    // pub fn clear_frame(&mut self, color: u16) -> Result<(), DisplayError> {
+    /// Streams colors from an iterator into the current memory write.
+    ///
+    /// Each color is serialized in the configured [`ByteOrder`] into a small stack buffer of
+    /// [`COLOR_CHUNK_PIXELS`] pixels, which is sent whenever it fills up, so a procedural
+    /// generator can feed the panel lazily without a framebuffer. Like
+    /// [`append_data`](Self::append_data) this does not set a window: start the write first,
+    /// e.g. with `lcd.begin_write(x0, y0, x1, y1)?.end()?`, and supply exactly as many colors
+    /// as the window holds.
+    pub fn write_colors(
+        &mut self,
+        colors: impl IntoIterator<Item = Rgb565>,
+    ) -> Result<(), DriverError<SPI, RST, WR, PWR>> {
+        let byte_order = self.byte_order;
+        self.write_pixel_bytes(
+            colors
+                .into_iter()
+                .map(|color| byte_order.bytes(color.into_storage())),
+        )
+    }
+    /// Streams two-byte pixels into a started memory write, batched on the stack
+    fn write_pixel_bytes(
+        &mut self,
+        pixels: impl Iterator<Item = [u8; 2]>,
+    ) -> Result<(), DriverError<SPI, RST, WR, PWR>> {
+        let mut chunk = [0u8; COLOR_CHUNK_PIXELS * 2];
+        let mut len = 0;
+        let mut dc_high = false;
+        for pixel in pixels {
+            chunk[len..len + 2].copy_from_slice(&pixel);
+            len += 2;
+            if len == chunk.len() {
+                self.send_pixel_chunk(&chunk, &mut dc_high)?;
+                len = 0;
+            }
+        }
+        if len > 0 {
+            self.send_pixel_chunk(&chunk[..len], &mut dc_high)?;
+        }
+        self.interface.flush()
+    }
+    /// Queues `chunk` of a memory write, driving DC high only for the first chunk
+    fn send_pixel_chunk(
+        &mut self,
+        chunk: &[u8],
+        dc_high: &mut bool,
+    ) -> Result<(), DriverError<SPI, RST, WR, PWR>> {
+        if core::mem::replace(dc_high, true) {
+            self.interface.data_continue(chunk)
+        } else {
+            self.interface.data_async(chunk)
+        }
+    }
+    /// Fills the whole panel with one raw RGB565 color without a framebuffer.
+    pub fn fill_frame(&mut self, color: u16) -> Result<(), DriverError<SPI, RST, WR, PWR>> {
+        let Geometry { width, height } = self.geometry;
+        self.fill_rect(0, 0, width, height, color)
+    }
+    /// Fills a `width` x `height` rectangle at `(x, y)` with one raw RGB565 color.
+    ///
+    /// The rectangle is clipped to the panel. One row's worth of pixels is prepared on the
+    /// stack and streamed repeatedly within a single `RAMWR` burst.
+    pub fn fill_rect(
+        &mut self,
+        x: u16,
+        y: u16,
+        width: u16,
+        height: u16,
+        color: u16,
+    ) -> Result<(), DriverError<SPI, RST, WR, PWR>> {
+        self.fill_rect_with_scratch::<MAX_LINE_SIZE>(x, y, width, height, color)
+    }
+    /// Fills a rectangle like [`fill_rect`](Self::fill_rect), using a stack scratch buffer of
+    /// `SCRATCH` bytes.
+    ///
+    /// The buffer is filled with the color once and then sent repeatedly, one SPI write per
+    /// `SCRATCH` bytes. A larger buffer means fewer, longer transfers (cheaper with DMA or
+    /// costly per-write setup) at the price of stack; a full frame takes 600 writes with 256
+    /// bytes and 240 with the default of one landscape row (640 bytes). `SCRATCH` is rounded
+    /// down to whole pixels and must be at least 2.
+    pub fn fill_rect_with_scratch<const SCRATCH: usize>(
+        &mut self,
+        x: u16,
+        y: u16,
+        width: u16,
+        height: u16,
+        color: u16,
+    ) -> Result<(), DriverError<SPI, RST, WR, PWR>> {
+        const { assert!(SCRATCH >= 2, "scratch buffer must hold at least one pixel") };
+        let end_x = (x as u32 + width as u32).min(self.geometry.width as u32);
+        let end_y = (y as u32 + height as u32).min(self.geometry.height as u32);
+        if x as u32 >= end_x || y as u32 >= end_y {
+            return Ok(());
+        }
+        let mut scratch = [0u8; SCRATCH];
+        let bytes = self.byte_order.bytes(color);
+        scratch
+            .chunks_exact_mut(2)
+            .for_each(|pixel| pixel.copy_from_slice(&bytes));
+        let chunk = SCRATCH & !1;
+        let mut remaining = (end_x - x as u32) as usize * (end_y - y as u32) as usize * 2;
+        let mut session = self.begin_write(x, y, end_x as u16 - 1, end_y as u16 - 1)?;
+        while remaining > 0 {
+            let len = remaining.min(chunk);
+            session.push_slice(&scratch[..len])?;
+            remaining -= len;
+        }
+        session.end()
+    }
+    /// Draws a horizontal line of `len` pixels starting at `(x, y)`, clipped to the panel.
+    pub fn draw_hline(
+        &mut self,
+        x: u16,
+        y: u16,
+        len: u16,
+        color: u16,
+    ) -> Result<(), DriverError<SPI, RST, WR, PWR>> {
+        self.fill_rect(x, y, len, 1, color)
+    }
+    /// Draws a vertical line of `len` pixels starting at `(x, y)`, clipped to the panel.
+    pub fn draw_vline(
+        &mut self,
+        x: u16,
+        y: u16,
+        len: u16,
+        color: u16,
+    ) -> Result<(), DriverError<SPI, RST, WR, PWR>> {
+        self.fill_rect(x, y, 1, len, color)
+    }
+    /// Writes a raw RGB565 image into a rectangular window of the display.
+    ///
+    /// The frame area is set to the `width` x `height` rectangle whose top-left corner is
+    /// `(x, y)`, then `data` is streamed in a single `RAMWR` burst. Pixels are expected in
+    /// row-major order, two bytes per pixel, MSB first.
+    ///
+    /// # Returns
+    /// - `Ok(())` if the image was written (or is empty).
+    /// - `Err(Error::InvalidFormat)` if `data.len()` is not `width * height * 2`.
+    /// - `Err(Error::OutOfBounds)` if the rectangle does not fit on the display.
+    /// - `Err(Error::Pin)` or `Err(Error::Spi)` if sending a command or data fails.
+    pub fn draw_image(
+        &mut self,
+        x: u16,
+        y: u16,
+        width: u16,
+        height: u16,
+        data: &[u8],
+    ) -> Result<(), DriverError<SPI, RST, WR, PWR>> {
+        if data.len() != frame_size(width, height, 16) {
+            return Err(Error::InvalidFormat);
+        }
+        if width == 0 || height == 0 {
+            return Ok(());
+        }
+        if x as u32 + width as u32 > self.geometry.width as u32
+            || y as u32 + height as u32 > self.geometry.height as u32
+        {
+            return Err(Error::OutOfBounds);
+        }
+        self.set_frame_area(x, y, x + width - 1, y + height - 1)?;
+        self.interface.write_command(Cmd::RAMWR.bits(), data)
+    }
+    // pub fn clear_frame(&mut self, color: u16) -> Result<(), DriverError<SPI, RST, WR, PWR>> {
     //     self.set_frame_area(0, 0, COLS - 1, ROWS - 1)?;
     //     let msb = (color >> 8) as u8;
     //     let lsb = color as u8;
@@ -187,46 +1387,2003 @@ where
     //     self.interface.data(&buffer)?;
     //     Ok(())
     // }
-    pub fn clear_frame(&mut self, display: &Display2in14) -> Result<(), DisplayError> {
-        self.set_frame_area(0, 0, COLS - 1, ROWS - 1)?;
+    /// Sends the content of a [`Display2in14`] buffer to the display.
+    pub fn clear_frame<const N: usize>(
+        &mut self,
+        display: &Display2in14<N>,
+    ) -> Result<(), DriverError<SPI, RST, WR, PWR>> {
+        self.flush(display)
+    }
+    /// Sends a [`Display2in14`] rotated by `rotation`, without changing MADCTL or allocating a
+    /// second buffer.
+    ///
+    /// The buffer is read in its stored (unrotated) layout and shown as if it had been drawn
+    /// with `rotation`, placed at the top-left of the panel: for `Rotate90` and `Rotate270` a
+    /// buffer `w` pixels wide and `h` rows high covers an `h` x `w` window. Pixels are
+    /// gathered in panel scan order into a small stack buffer and streamed in one `RAMWR`, so
+    /// their bytes and byte order are sent unchanged.
+    ///
+    /// Use it when the scan direction must stay fixed; otherwise prefer
+    /// [`set_hardware_rotation`](Self::set_hardware_rotation). Reading the source column by
+    /// column costs CPU time for every pixel and splits the upload into 128-byte writes (1200
+    /// for a full frame), whereas a MADCTL rotation keeps [`flush`](Self::flush) a single
+    /// burst with no extra work.
+    ///
+    /// Returns [`Error::OutOfBounds`] if the rotated buffer does not fit the panel in its
+    /// current geometry, and [`Error::InvalidFormat`] if the panel is not set to RGB565.
+    pub fn flush_rotated<const N: usize>(
+        &mut self,
+        display: &Display2in14<N>,
+        rotation: DisplayRotation,
+    ) -> Result<(), DriverError<SPI, RST, WR, PWR>> {
+        if self
+            .pixel_format
+            .is_some_and(|format| format != PixelFormat::Rgb565)
+        {
+            return Err(Error::InvalidFormat);
+        }
+        let bytes = display.raw_bytes();
+        let width = display.get_geometry().width as usize;
+        let height = bytes.len() / PixelFormat::Rgb565.bytes_for(width);
+        let (out_width, out_height) = match rotation {
+            DisplayRotation::Rotate0 | DisplayRotation::Rotate180 => (width, height),
+            DisplayRotation::Rotate90 | DisplayRotation::Rotate270 => (height, width),
+        };
+        if out_width > self.geometry.width as usize || out_height > self.geometry.height as usize {
+            return Err(Error::OutOfBounds);
+        }
+        self.set_frame_area(0, 0, out_width as u16 - 1, out_height as u16 - 1)?;
+        self.interface.cmd(Cmd::RAMWR.bits())?;
+        self.write_pixel_bytes((0..out_height).flat_map(|y| {
+            (0..out_width).map(move |x| {
+                let (x, y) = rotate(rotation, x, y, width, height);
+                let at = (y * width + x) * 2;
+                [bytes[at], bytes[at + 1]]
+            })
+        }))?;
+        self.frame_flushed()
+    }
+    /// Sends a [`Display2in14Packed`] to the top of the panel, converting each pixel to RGB565
+    /// on the way.
+    ///
+    /// The conversion happens in a small stack buffer while streaming one `RAMWR`, so the
+    /// packed buffer stays the only frame in RAM; the cost is some CPU time per pixel and
+    /// 128-byte SPI writes instead of one burst. Returns [`Error::InvalidFormat`] if the panel
+    /// is not set to RGB565, and [`Error::OutOfBounds`] if the buffer is laid out for another
+    /// [`Geometry`] than the panel.
+    pub fn flush_packed<C: PackedColor, const N: usize>(
+        &mut self,
+        display: &Display2in14Packed<C, N>,
+    ) -> Result<(), DriverError<SPI, RST, WR, PWR>> {
+        if display.get_geometry() != self.geometry {
+            return Err(Error::OutOfBounds);
+        }
+        if self
+            .pixel_format
+            .is_some_and(|format| format != PixelFormat::Rgb565)
+        {
+            return Err(Error::InvalidFormat);
+        }
+        self.set_frame_area(0, 0, self.geometry.width - 1, display.lines() - 1)?;
+        self.interface.cmd(Cmd::RAMWR.bits())?;
+        let byte_order = self.byte_order;
+        self.write_pixel_bytes(
+            display
+                .rgb565_pixels()
+                .map(|color| byte_order.bytes(color.into_storage())),
+        )?;
+        self.frame_flushed()
+    }
+    /// Sends the content of a [`WindowBuffer`] to its window on the display.
+    pub fn flush_window<const N: usize>(
+        &mut self,
+        window: &WindowBuffer<N>,
+    ) -> Result<(), DriverError<SPI, RST, WR, PWR>> {
+        self.flush(window)
+    }
+    /// Sends the content of any [`Framebuffer`] to the display.
+    ///
+    /// The frame area is set to the rectangle reported by [`Framebuffer::area`], then the bytes
+    /// from [`Framebuffer::bytes`] are streamed in a single `RAMWR` burst.
+    pub fn flush(
+        &mut self,
+        framebuffer: &impl Framebuffer,
+    ) -> Result<(), DriverError<SPI, RST, WR, PWR>> {
+        if self
+            .pixel_format
+            .is_some_and(|format| format != framebuffer.pixel_format())
+        {
+            return Err(Error::InvalidFormat);
+        }
+        let (start_x, start_y, end_x, end_y) = framebuffer.area();
+        self.set_frame_area(start_x, start_y, end_x, end_y)?;
+        self.interface.cmd(Cmd::RAMWR.bits())?;
+        self.interface.data_nowait(framebuffer.bytes())?;
+        self.interface.flush()?;
+        self.frame_flushed()
+    }
+    /// Sends the same [`Framebuffer`] to several panels, e.g. two mirrored modules with
+    /// their own CS and DC lines.
+    ///
+    /// The buffer is only borrowed, so no copy is made. Each panel is flushed in turn, and a
+    /// failing one does not stop the others; the first error is returned after all have been
+    /// tried. Every `Lcd` needs its own `SpiBus` handle that selects its panel.
+    pub fn flush_to_all(
+        lcds: &mut [&mut Self],
+        framebuffer: &impl Framebuffer,
+    ) -> Result<(), DriverError<SPI, RST, WR, PWR>> {
+        let mut first = Ok(());
+        for lcd in lcds {
+            let result = lcd.flush(framebuffer);
+            if first.is_ok() {
+                first = result;
+            }
+        }
+        first
+    }
+    /// Sends any [`Framebuffer`] like [`flush`](Self::flush), calling `on_chunk` with the
+    /// number of bytes sent so far after every `chunk_len` bytes.
+    ///
+    /// A full frame takes tens of milliseconds on a slow bus; the callback lets firmware kick a
+    /// watchdog or advance a progress indicator during the upload. Each chunk is flushed
+    /// before the callback runs, so the count is what has left the bus. Smaller chunks call
+    /// back more often but add a bus flush each. A `chunk_len` of zero is treated as one byte.
+    pub fn flush_with_progress(
+        &mut self,
+        framebuffer: &impl Framebuffer,
+        chunk_len: usize,
+        mut on_chunk: impl FnMut(usize),
+    ) -> Result<(), DriverError<SPI, RST, WR, PWR>> {
+        if self
+            .pixel_format
+            .is_some_and(|format| format != framebuffer.pixel_format())
+        {
+            return Err(Error::InvalidFormat);
+        }
+        let (start_x, start_y, end_x, end_y) = framebuffer.area();
+        self.set_frame_area(start_x, start_y, end_x, end_y)?;
+        self.interface.cmd(Cmd::RAMWR.bits())?;
+        let mut sent = 0;
+        for chunk in framebuffer.bytes().chunks(chunk_len.max(1)) {
+            self.interface.data(chunk)?;
+            sent += chunk.len();
+            on_chunk(sent);
+        }
+        self.frame_flushed()
+    }
+    /// Switches the backlight on if it was deferred until a frame had been flushed
+    fn frame_flushed(&mut self) -> Result<(), DriverError<SPI, RST, WR, PWR>> {
+        if self.backlight_pending {
+            self.interface.set_backlight(u8::MAX)?;
+            self.backlight_pending = false;
+        }
+        Ok(())
+    }
+    /// Enables the tearing-effect output (TEON, 0x35) in V-blank mode.
+    ///
+    /// The TE pin of the panel then goes high while the controller is not scanning out GRAM.
+    pub fn tearing_effect_on(&mut self) -> Result<(), DriverError<SPI, RST, WR, PWR>> {
+        self.interface.write_command(Cmd::TEON.bits(), &[0x00])
+    }
+    /// Disables the tearing-effect output (TEOFF, 0x34).
+    pub fn tearing_effect_off(&mut self) -> Result<(), DriverError<SPI, RST, WR, PWR>> {
+        self.interface.write_command(Cmd::TEOFF.bits(), &[])
+    }
+    /// Waits for the start of vertical blanking on `te`, then [`flush`](Self::flush)es
+    /// `framebuffer`.
+    ///
+    /// Call [`tearing_effect_on`](Self::tearing_effect_on) once beforehand. The upload starts
+    /// on a rising edge of the TE signal, so the controller scans out the new frame behind the
+    /// write instead of overtaking it. For a tear-free full frame the upload must outpace the
+    /// refresh; at 40 MHz SPI it takes about 31 ms, so combine this with a lower
+    /// [`FrameRate`] or a smaller window.
+    ///
+    /// `te` is polled every microsecond. If no rising edge is seen within roughly `timeout_us`
+    /// microseconds (a frame at 60 Hz lasts about 16700), [`Error::Timeout`] is returned and
+    /// nothing is sent, so a disconnected TE line cannot hang the caller.
+    pub fn flush_synced(
+        &mut self,
+        framebuffer: &impl Framebuffer,
+        te: &mut impl InputPin,
+        delay: &mut impl DelayNs,
+        timeout_us: u32,
+    ) -> Result<(), DriverError<SPI, RST, WR, PWR>> {
+        let mut budget = timeout_us;
+        Self::wait_for_level(te, delay, false, &mut budget)?;
+        Self::wait_for_level(te, delay, true, &mut budget)?;
+        self.flush(framebuffer)
+    }
+    /// Waits for the start of vertical blanking on `te`, then uploads only `rows` of
+    /// `framebuffer`.
+    ///
+    /// `rows` counts buffer rows from the top of [`Framebuffer::area`] and is clamped to it;
+    /// for a rotated [`Display2in14`] they are physical rows, as in
+    /// [`Display2in14::region_rows`]. The rows are sent as one window spanning the buffer's
+    /// width, so a short upload fits easily in the blanking period and bus traffic stays
+    /// proportional to what changed.
+    ///
+    /// For small animations such as a spinner or a gauge needle, redraw the element, take the
+    /// rows of its old and new bounding boxes, and pass their union here once per frame.
+    /// Waiting and the timeout behave as in [`flush_synced`](Self::flush_synced); an empty
+    /// range returns at once without waiting.
+    pub fn flush_dirty_synced(
+        &mut self,
+        framebuffer: &impl Framebuffer,
+        rows: Range<u16>,
+        te: &mut impl InputPin,
+        delay: &mut impl DelayNs,
+        timeout_us: u32,
+    ) -> Result<(), DriverError<SPI, RST, WR, PWR>> {
+        if self
+            .pixel_format
+            .is_some_and(|format| format != framebuffer.pixel_format())
+        {
+            return Err(Error::InvalidFormat);
+        }
+        let (_, start_y, _, end_y) = framebuffer.area();
+        let height = end_y - start_y + 1;
+        let rows = rows.start.min(height)..rows.end.min(height);
+        if rows.is_empty() {
+            return Ok(());
+        }
+        let mut budget = timeout_us;
+        Self::wait_for_level(te, delay, false, &mut budget)?;
+        Self::wait_for_level(te, delay, true, &mut budget)?;
+        self.flush_rows(framebuffer, rows.start as usize..rows.end as usize)
+    }
+    /// Sends `rows` of `framebuffer`, counted from the top of its area, as one window
+    fn flush_rows(
+        &mut self,
+        framebuffer: &impl Framebuffer,
+        rows: Range<usize>,
+    ) -> Result<(), DriverError<SPI, RST, WR, PWR>> {
+        let (start_x, start_y, end_x, _) = framebuffer.area();
+        let stride = framebuffer
+            .pixel_format()
+            .bytes_for((end_x - start_x) as usize + 1);
+        self.set_frame_area(
+            start_x,
+            start_y + rows.start as u16,
+            end_x,
+            start_y + rows.end as u16 - 1,
+        )?;
         self.interface.cmd(Cmd::RAMWR.bits())?;
-        self.interface.data(&display.buffer)
-    }
-}
-
-// impl<SPI, RST, WR, PWR> Dimensions for Lcd<SPI, RST, WR, PWR>
-// where
-//     SPI: SpiBus,
-//     RST: OutputPin,
-//     WR: OutputPin,
-//     PWR: OutputPin,
-// {
-//     fn bounding_box(&self) -> embedded_graphics::primitives::Rectangle {
-//         embedded_graphics::primitives::Rectangle::new(
-//             Point { x: 0, y: 0 },
-//             Size::new(COLS as u32, ROWS as u32)
-//         )
-//     }
-// }
-
-// impl<SPI, RST, WR, PWR> DrawTarget for Lcd<SPI, RST, WR, PWR>
-// where
-//     SPI: SpiBus,
-//     RST: OutputPin,
-//     WR: OutputPin,
-//     PWR: OutputPin,
-// {
-//     type Color = embedded_graphics::pixelcolor::Rgb565;
-
-//     type Error = display_interface::DisplayError;
-
-//     fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
-//     where
-//         I: IntoIterator<Item = embedded_graphics::Pixel<Self::Color>>,
-//     {
-//         pixels.into_iter().try_for_each(|pixel| {
-//             let ((x, y), color) = ((pixel.0.x as u16, pixel.0.y as u16), pixel.1);
-//             self.set_pixel(x, y, color.into_storage())
-//         })
-//     }
-// }
+        self.interface
+            .data_nowait(&framebuffer.bytes()[rows.start * stride..rows.end * stride])?;
+        // The next run starts with CASET, so the bus must be idle before DC goes low
+        self.interface.flush()
+    }
+    /// Polls `pin` until it reads `high`, spending microseconds from `budget`
+    fn wait_for_level(
+        pin: &mut impl InputPin,
+        delay: &mut impl DelayNs,
+        high: bool,
+        budget: &mut u32,
+    ) -> Result<(), DriverError<SPI, RST, WR, PWR>> {
+        while pin
+            .is_high()
+            .map_err(|e| Error::TearingEffect(digital::Error::kind(&e)))?
+            != high
+        {
+            if *budget == 0 {
+                return Err(Error::Timeout);
+            }
+            delay.delay_us(1);
+            *budget -= 1;
+        }
+        Ok(())
+    }
+}
+
+impl<DI, RST, PWR> Lcd<DiBus<DI>, RST, NoDcPin, PWR>
+where
+    DI: WriteOnlyDataCommand,
+    RST: ResetPin,
+    PWR: Backlight,
+{
+    /// Initializes the display through an existing `display-interface` object, like
+    /// [`init`](Self::init).
+    ///
+    /// `di` replaces the SPI bus and DC pin (see [`DiBus`]). Register reads are not available
+    /// through a write-only interface and return an error.
+    ///
+    /// ```rust,ignore
+    /// let di = SPIInterface::new(spi_device, dc);
+    /// let mut lcd = Lcd::init_with_interface(di, rst, backlight, &mut delay);
+    /// ```
+    ///
+    /// **Note:** A DC line behind a GPIO expander or other wrapper does not need this: any
+    /// `OutputPin` works as `wr` with [`init`](Self::init).
+    pub fn init_with_interface(di: DI, rst: RST, pwr: PWR, delay: &mut impl DelayNs) -> Self {
+        Self::from_di(di, rst, pwr, delay, InitOptions::default())
+    }
+    /// Initializes the display through a `display-interface` object with the given
+    /// [`InitOptions`], like [`init_with_options`](Self::init_with_options).
+    ///
+    /// Every command byte goes to [`WriteOnlyDataCommand::send_commands`] and every parameter
+    /// or pixel slice to [`WriteOnlyDataCommand::send_data`] as `DataFormat::U8`, so
+    /// implementations such as `display-interface-spi`'s `SPIInterface` see whole frames and
+    /// can batch them as they do for other drivers. The data/command level is kept in the
+    /// driver's own [`DiBus`], so several panels can be driven side by side. The raw `SpiBus`
+    /// constructors stay the dependency-free path.
+    pub fn from_di(
+        di: DI,
+        rst: RST,
+        pwr: PWR,
+        delay: &mut impl DelayNs,
+        options: InitOptions,
+    ) -> Self {
+        Self::init_with_options(DiBus::new(di), rst, NoDcPin, pwr, delay, options)
+    }
+    /// Sets the frame area like [`set_frame_area`](Self::set_frame_area), but sends `CASET`,
+    /// `RASET` and their parameters back to back and waits for the bus once at the end.
+    ///
+    /// Only the `display-interface` backend benefits: each `send_commands` and `send_data`
+    /// call is a complete transaction that selects data or command itself, and on an
+    /// `SpiDevice` frames its own chip select, so nothing is left in flight when the next call
+    /// changes the level. On the `SpiBus` and DC pin backend the flushes between the writes are
+    /// what keep DC from toggling under bytes still being clocked out, so it has no batched
+    /// variant.
+    pub fn set_frame_area_batched(
+        &mut self,
+        start_x: u16,
+        start_y: u16,
+        end_x: u16,
+        end_y: u16,
+    ) -> Result<(), DriverError<DiBus<DI>, RST, NoDcPin, PWR>> {
+        let [x0_hi, x0_lo] = start_x.to_be_bytes();
+        let [x1_hi, x1_lo] = end_x.to_be_bytes();
+        let [y0_hi, y0_lo] = start_y.to_be_bytes();
+        let [y1_hi, y1_lo] = end_y.to_be_bytes();
+        self.interface.cmd_async(Cmd::CASET.bits())?;
+        self.interface.data_async(&[x0_hi, x0_lo, x1_hi, x1_lo])?;
+        self.interface.cmd_async(Cmd::RASET.bits())?;
+        self.interface.data_async(&[y0_hi, y0_lo, y1_hi, y1_lo])?;
+        self.interface.flush()
+    }
+}
+
+/// Result of [`Lcd::begin_write`]
+type SessionResult<'a, SPI, RST, WR, PWR> =
+    Result<WriteSession<'a, SPI, RST, WR, PWR>, DriverError<SPI, RST, WR, PWR>>;
+
+/// An in-progress memory write started with [`Lcd::begin_write`].
+///
+/// Each pushed pixel advances the controller's internal address: the column increments from
+/// `start_x` to `end_x`, then wraps back to `start_x` on the next row, until `end_y` is reached.
+/// Writing past the last pixel of the window wraps around to `(start_x, start_y)`.
+///
+/// Pushes are not individually flushed; the SPI bus is flushed when the session is dropped or
+/// ended. The session holds a mutable borrow of the `Lcd` for its whole lifetime.
+///
+/// DC is driven high by the first push after `RAMWR`; later pushes skip the pin write.
+pub struct WriteSession<'a, SPI, RST, WR, PWR>
+where
+    SPI: LcdBus<WR>,
+    RST: ResetPin,
+    WR: digital::ErrorType,
+    PWR: Backlight,
+{
+    lcd: &'a mut Lcd<SPI, RST, WR, PWR>,
+    /// Window set by `begin_write`, inclusive
+    window: (u16, u16, u16, u16),
+    /// Bytes written into the window so far, modulo its size
+    offset: usize,
+    /// Offset of the next row start, when resumed mid-row into a one-row window
+    row_break: Option<usize>,
+    /// Whether DC has been driven high since the last `RAMWR`
+    dc_high: bool,
+    /// Whether the window must be set again before the next push, after an interruption or
+    /// a failed resume
+    resume_pending: bool,
+}
+
+impl<SPI, RST, WR, PWR> WriteSession<'_, SPI, RST, WR, PWR>
+where
+    SPI: LcdBus<WR>,
+    RST: ResetPin,
+    WR: digital::ErrorType,
+    PWR: Backlight,
+{
+    /// Writes one RGB565 pixel and advances to the next pixel in the window.
+    #[inline]
+    pub fn push(&mut self, color: u16) -> Result<(), DriverError<SPI, RST, WR, PWR>> {
+        let bytes = self.lcd.byte_order.bytes(color);
+        self.push_slice(&bytes)
+    }
+    /// Writes raw RGB565 bytes (two per pixel, MSB first) and advances past them.
+    #[inline]
+    pub fn push_slice(&mut self, mut data: &[u8]) -> Result<(), DriverError<SPI, RST, WR, PWR>> {
+        if self.resume_pending {
+            self.resume()?;
+        }
+        if let Some(row_break) = self
+            .row_break
+            .filter(|&row_break| data.len() >= row_break - self.offset)
+        {
+            let (head, tail) = data.split_at(row_break - self.offset);
+            self.send(head)?;
+            // The rest of the row must leave the bus before `CASET` drives DC low
+            self.lcd.interface.flush()?;
+            self.offset = row_break % self.window_len();
+            self.row_break = None;
+            self.resume()?;
+            data = tail;
+        }
+        if !data.is_empty() {
+            self.send(data)?;
+            self.offset = (self.offset + data.len()) % self.window_len();
+        }
+        Ok(())
+    }
+    /// Queues `data`, driving DC high only for the first slice after `RAMWR`
+    #[inline]
+    fn send(&mut self, data: &[u8]) -> Result<(), DriverError<SPI, RST, WR, PWR>> {
+        if core::mem::replace(&mut self.dc_high, true) {
+            self.lcd.interface.data_continue(data)
+        } else {
+            self.lcd.interface.data_async(data)
+        }
+    }
+    /// Pauses the write to run `f` on the `Lcd`, then resumes at the next pixel of the window.
+    ///
+    /// Any command ends a memory write, so `f` is free to adjust the backlight, send a MADCTL
+    /// or draw elsewhere. Afterwards `CASET`/`RASET`/`RAMWR` are re-issued for the rest of the
+    /// window; if the write stopped mid-row, the rest of that row is written through a
+    /// one-row window first and the full-width window is restored at the next row.
+    ///
+    /// **Note:** The controller does not report its GRAM position, so the resume point is
+    /// derived from the bytes pushed through this session; interrupt only between whole
+    /// pixels. After an interruption, writing past the last pixel wraps to the start of the
+    /// resumed rows instead of `(start_x, start_y)`.
+    ///
+    /// The write is resumed even if `f` fails, and `f`'s error is returned afterwards. If
+    /// resuming itself fails, the next push retries it and returns its error until it succeeds,
+    /// so pixel data never lands as parameters of a command sent by `f`.
+    pub fn with_interruption<R>(
+        &mut self,
+        f: impl FnOnce(&mut Lcd<SPI, RST, WR, PWR>) -> Result<R, DriverError<SPI, RST, WR, PWR>>,
+    ) -> Result<R, DriverError<SPI, RST, WR, PWR>> {
+        self.lcd.interface.flush()?;
+        self.resume_pending = true;
+        let result = f(self.lcd);
+        self.resume()?;
+        result
+    }
+    /// Sets the window again from the current offset and restarts the memory write
+    fn resume(&mut self) -> Result<(), DriverError<SPI, RST, WR, PWR>> {
+        // Until `RAMWR` is sent again, neither DC nor the GRAM position can be trusted
+        self.resume_pending = true;
+        let (start_x, _, end_x, _) = self.window;
+        let stride = self.stride();
+        if self.offset.is_multiple_of(stride) {
+            self.row_break = None;
+            self.resume_at(self.offset)?;
+        } else {
+            let x = start_x + (self.offset % stride / 2) as u16;
+            let y = self.row_y(self.offset);
+            self.lcd.set_frame_area(x, y, end_x, y)?;
+            self.lcd.interface.cmd(Cmd::RAMWR.bits())?;
+            self.dc_high = false;
+            self.row_break = Some(self.offset.next_multiple_of(stride));
+        }
+        self.resume_pending = false;
+        Ok(())
+    }
+    /// Restarts the memory write at the row starting `offset` bytes into the window
+    fn resume_at(&mut self, offset: usize) -> Result<(), DriverError<SPI, RST, WR, PWR>> {
+        let (start_x, _, end_x, end_y) = self.window;
+        let offset = offset % self.window_len();
+        self.lcd
+            .set_frame_area(start_x, self.row_y(offset), end_x, end_y)?;
+        self.lcd.interface.cmd(Cmd::RAMWR.bits())?;
+        self.dc_high = false;
+        self.offset = offset;
+        Ok(())
+    }
+    /// Panel row holding the byte `offset` bytes into the window
+    fn row_y(&self, offset: usize) -> u16 {
+        self.window.1 + (offset / self.stride()) as u16
+    }
+    /// Bytes per window row
+    fn stride(&self) -> usize {
+        let (start_x, _, end_x, _) = self.window;
+        (end_x - start_x) as usize * 2 + 2
+    }
+    /// Bytes in the whole window
+    fn window_len(&self) -> usize {
+        let (_, start_y, _, end_y) = self.window;
+        self.stride() * ((end_y - start_y) as usize + 1)
+    }
+    /// Ends the session, waiting until all pushed data has been sent.
+    ///
+    /// Dropping the session does the same but discards any error.
+    pub fn end(self) -> Result<(), DriverError<SPI, RST, WR, PWR>> {
+        // Skip `Drop`, which would flush a second time
+        let mut session = core::mem::ManuallyDrop::new(self);
+        session.lcd.interface.flush()
+    }
+}
+
+impl<SPI, RST, WR, PWR> Drop for WriteSession<'_, SPI, RST, WR, PWR>
+where
+    SPI: LcdBus<WR>,
+    RST: ResetPin,
+    WR: digital::ErrorType,
+    PWR: Backlight,
+{
+    fn drop(&mut self) {
+        let _ = self.lcd.interface.flush();
+    }
+}
+
+/// Read-back support.
+///
+/// These methods clock data out of the controller, so the panel's serial output must be
+/// connected to the SPI peripheral's MISO (or a readable shared SDA line).
+impl<SPI, RST, WR, PWR> Lcd<SPI, RST, WR, PWR>
+where
+    SPI: LcdBus<WR>,
+    RST: ResetPin,
+    WR: digital::ErrorType,
+    PWR: Backlight,
+{
+    /// Detects whether the panel stores colors in RGB or BGR order.
+    ///
+    /// A pure red pixel is written at `(0, 0)` and read back with `RAMRD`. Memory reads return
+    /// one dummy byte followed by three bytes per pixel (one per channel, 6 bits left-aligned),
+    /// so the order is decided by whether the red value comes back in the first or the last
+    /// channel.
+    ///
+    /// Returns [`Error::InvalidFormat`] unless exactly one outer channel reads back near full
+    /// scale and the other two near zero. An unconnected MISO line reads as all zeros or all
+    /// ones, so it is reported as an error rather than as a color order.
+    ///
+    /// **Note:** The pixel at `(0, 0)` is overwritten.
+    pub fn detect_color_order(&mut self) -> Result<ColorOrder, DriverError<SPI, RST, WR, PWR>> {
+        self.set_pixel(0, 0, 0xF800)?;
+        let mut buf = [0u8; 4];
+        self.interface.read(Cmd::RAMRD.bits(), &mut buf)?;
+        let full = |channel: u8| channel >= 0xC0;
+        let dark = |channel: u8| channel < 0x40;
+        match (buf[1], buf[2], buf[3]) {
+            (first, second, last) if full(first) && dark(second) && dark(last) => {
+                Ok(ColorOrder::Rgb)
+            }
+            (first, second, last) if dark(first) && dark(second) && full(last) => {
+                Ok(ColorOrder::Bgr)
+            }
+            _ => Err(Error::InvalidFormat),
+        }
+    }
+    /// Reads the display power mode (`RDDPM`, 0x0A).
+    ///
+    /// Bits of interest: D7 booster on, D4 sleep out, D3 normal mode, D2 display on.
+    pub fn read_power_mode(&mut self) -> Result<u8, DriverError<SPI, RST, WR, PWR>> {
+        let mut buf = [0u8; 1];
+        self.interface.read(Cmd::RDDPM.bits(), &mut buf)?;
+        Ok(buf[0])
+    }
+    /// Reads the MADCTL value the controller is actually using (`RDDMADCTL`, 0x0B).
+    ///
+    /// Compare it with the value last written through [`mem_data_ac`](Self::mem_data_ac) and
+    /// friends to spot a mismatch between commanded and actual state.
+    pub fn read_madctl(&mut self) -> Result<u8, DriverError<SPI, RST, WR, PWR>> {
+        let mut buf = [0u8; 1];
+        self.interface.read(Cmd::RDDMADCTL.bits(), &mut buf)?;
+        Ok(buf[0])
+    }
+    /// Reads the result of the controller's self-diagnostics (`RDDSDR`, 0x0F).
+    ///
+    /// Check for [`SelfDiagnostic::all`] after power-up to confirm the panel passed its
+    /// register-loading and functionality checks. Like the other single-byte status reads it
+    /// has no dummy clock cycle in serial mode, so exactly one byte is clocked in; bits other
+    /// than the two defined flags are discarded.
+    pub fn read_self_diagnostic(
+        &mut self,
+    ) -> Result<SelfDiagnostic, DriverError<SPI, RST, WR, PWR>> {
+        let mut buf = [0u8; 1];
+        self.interface.read(Cmd::RDDSDR.bits(), &mut buf)?;
+        Ok(SelfDiagnostic::from_bits_truncate(buf[0]))
+    }
+}
+
+impl<SPI, RST, WR, PWR> OriginDimensions for Lcd<SPI, RST, WR, PWR> {
+    fn size(&self) -> Size {
+        Size::new(self.geometry.width as u32, self.geometry.height as u32)
+    }
+}
+
+/// Drawing directly to the panel without a framebuffer.
+///
+/// Incoming pixels are coalesced into horizontal spans: consecutive pixels on the same row,
+/// with contiguous `x` and the same color, are sent as a single windowed `RAMWR` burst. Filled
+/// shapes therefore cost one window per row instead of one per pixel. Pixels outside the panel
+/// are skipped.
+impl<SPI, RST, WR, PWR> DrawTarget for Lcd<SPI, RST, WR, PWR>
+where
+    SPI: LcdBus<WR>,
+    RST: ResetPin,
+    WR: digital::ErrorType,
+    PWR: Backlight,
+{
+    type Color = Rgb565;
+
+    type Error = DriverError<SPI, RST, WR, PWR>;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        let mut span: Option<Span> = None;
+        for Pixel(point, color) in pixels {
+            if point.x < 0
+                || point.y < 0
+                || point.x >= self.geometry.width as i32
+                || point.y >= self.geometry.height as i32
+            {
+                continue;
+            }
+            let (x, y, color) = (point.x as u16, point.y as u16, color.into_storage());
+            if let Some(span) = span.as_mut() {
+                if span.y == y && span.end_x + 1 == x && span.color == color {
+                    span.end_x = x;
+                    continue;
+                }
+                self.fill_rect(
+                    span.start_x,
+                    span.y,
+                    span.end_x - span.start_x + 1,
+                    1,
+                    span.color,
+                )?;
+            }
+            span = Some(Span {
+                start_x: x,
+                end_x: x,
+                y,
+                color,
+            });
+        }
+        if let Some(span) = span {
+            self.fill_rect(
+                span.start_x,
+                span.y,
+                span.end_x - span.start_x + 1,
+                1,
+                span.color,
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// A run of same-colored pixels on one row, collected by `Lcd::draw_iter`
+struct Span {
+    start_x: u16,
+    end_x: u16,
+    y: u16,
+    color: u16,
+}
+
+/// Flushes only the rows that changed since the previous flush through it.
+///
+/// It keeps a shadow copy of the last framebuffer it uploaded and compares the new one row by
+/// row; each run of consecutive differing rows is sent as one window. This pays off for UIs
+/// with several small changes scattered over the screen, where a single dirty rectangle would
+/// cover most of it. The shadow costs as much RAM as the framebuffer (150 KB for a full
+/// frame), so it is only available with `heap_alloc`.
+///
+/// The first flush, and any flush whose area or size differs from the previous one, uploads
+/// the whole framebuffer. If anything else writes to the panel in between, call
+/// [`invalidate`](Self::invalidate) so the next flush is complete again.
+#[cfg(feature = "heap_alloc")]
+#[derive(Default)]
+pub struct FrameDiff {
+    shadow: Vec<u8>,
+    /// Area of the framebuffer held in `shadow`, `None` while it is not valid
+    area: Option<(u16, u16, u16, u16)>,
+}
+
+#[cfg(feature = "heap_alloc")]
+impl FrameDiff {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// Forgets the shadow copy, so the next flush uploads everything.
+    pub fn invalidate(&mut self) {
+        self.area = None;
+    }
+    /// Sends the rows of `framebuffer` that differ from the previous flush to `lcd`.
+    pub fn flush<SPI, RST, WR, PWR>(
+        &mut self,
+        lcd: &mut Lcd<SPI, RST, WR, PWR>,
+        framebuffer: &impl Framebuffer,
+    ) -> Result<(), DriverError<SPI, RST, WR, PWR>>
+    where
+        SPI: LcdBus<WR>,
+        RST: ResetPin,
+        WR: digital::ErrorType,
+        PWR: Backlight,
+    {
+        let area = framebuffer.area();
+        let bytes = framebuffer.bytes();
+        if self.area != Some(area) || self.shadow.len() != bytes.len() {
+            self.area = None;
+            lcd.flush(framebuffer)?;
+            self.shadow.clear();
+            self.shadow.extend_from_slice(bytes);
+            self.area = Some(area);
+            return Ok(());
+        }
+        if lcd
+            .pixel_format
+            .is_some_and(|format| format != framebuffer.pixel_format())
+        {
+            return Err(Error::InvalidFormat);
+        }
+        let (start_x, _, end_x, _) = area;
+        let stride = framebuffer
+            .pixel_format()
+            .bytes_for((end_x - start_x) as usize + 1);
+        let rows = bytes.len() / stride;
+        let mut row = 0;
+        while row < rows {
+            let changed = |row: usize| {
+                bytes[row * stride..(row + 1) * stride]
+                    != self.shadow[row * stride..(row + 1) * stride]
+            };
+            if !changed(row) {
+                row += 1;
+                continue;
+            }
+            let first = row;
+            while row < rows && changed(row) {
+                row += 1;
+            }
+            lcd.flush_rows(framebuffer, first..row)?;
+            let run = first * stride..row * stride;
+            self.shadow[run.clone()].copy_from_slice(&bytes[run]);
+        }
+        Ok(())
+    }
+}
+
+/// An [`Lcd`] that owns its delay provider.
+///
+/// The methods that wait (initialization, reset, sleep and display mode changes) use the stored
+/// delay, so it does not have to be passed to every call. Everything else is reached through
+/// `Deref` to [`Lcd`]. Use [`Lcd`] directly when one delay peripheral is shared between drivers.
+pub struct LcdWithDelay<SPI, RST, WR, PWR, D> {
+    lcd: Lcd<SPI, RST, WR, PWR>,
+    delay: D,
+}
+
+impl<SPI, RST, WR, PWR, D> LcdWithDelay<SPI, RST, WR, PWR, D>
+where
+    SPI: LcdBus<WR>,
+    RST: ResetPin,
+    WR: digital::ErrorType,
+    PWR: Backlight,
+    D: DelayNs,
+{
+    /// Initializes the display like [`Lcd::init`] and keeps `delay` for later calls.
+    pub fn init(spi: SPI, rst: RST, wr: WR, pwr: PWR, delay: D) -> Self {
+        Self::init_with_options(spi, rst, wr, pwr, delay, InitOptions::default())
+    }
+    /// Initializes the display like [`Lcd::init_with_options`] and keeps `delay` for later calls.
+    pub fn init_with_options(
+        spi: SPI,
+        rst: RST,
+        wr: WR,
+        pwr: PWR,
+        mut delay: D,
+        options: InitOptions,
+    ) -> Self {
+        let lcd = Lcd::init_with_options(spi, rst, wr, pwr, &mut delay, options);
+        Self { lcd, delay }
+    }
+    /// See [`Lcd::init_with_profile`].
+    pub fn init_with_profile(
+        spi: SPI,
+        rst: RST,
+        wr: WR,
+        pwr: PWR,
+        mut delay: D,
+        profile: &impl PanelProfile,
+        options: InitOptions,
+    ) -> Self {
+        let lcd = Lcd::init_with_profile(spi, rst, wr, pwr, &mut delay, profile, options);
+        Self { lcd, delay }
+    }
+    /// Wraps an already initialized [`Lcd`].
+    pub fn new(lcd: Lcd<SPI, RST, WR, PWR>, delay: D) -> Self {
+        Self { lcd, delay }
+    }
+    /// Returns the driver and the delay provider.
+    pub fn release(self) -> (Lcd<SPI, RST, WR, PWR>, D) {
+        (self.lcd, self.delay)
+    }
+    /// The stored delay provider, for waits between other calls
+    pub fn delay(&mut self) -> &mut D {
+        &mut self.delay
+    }
+    /// See [`Lcd::reset`].
+    pub fn reset(&mut self, timing: ResetTiming) -> Result<(), DriverError<SPI, RST, WR, PWR>> {
+        self.lcd.reset(timing, &mut self.delay)
+    }
+    /// See [`Lcd::recover`].
+    pub fn recover(
+        &mut self,
+        profile: &impl PanelProfile,
+    ) -> Result<(), DriverError<SPI, RST, WR, PWR>> {
+        self.lcd.recover(profile, &mut self.delay)
+    }
+    /// See [`Lcd::run_init_script`].
+    pub fn run_init_script(
+        &mut self,
+        script: &[InitStep],
+    ) -> Result<(), DriverError<SPI, RST, WR, PWR>> {
+        self.lcd.run_init_script(script, &mut self.delay)
+    }
+    /// See [`Lcd::run_init_script_batched`].
+    pub fn run_init_script_batched(
+        &mut self,
+        script: &[InitStep],
+    ) -> Result<(), DriverError<SPI, RST, WR, PWR>> {
+        self.lcd.run_init_script_batched(script, &mut self.delay)
+    }
+    /// Turn off sleep mode
+    pub fn sleep_out(&mut self) {
+        self.lcd.sleep_out(&mut self.delay)
+    }
+    /// Enter display inversion mode
+    pub fn display_inversion_on(&mut self) {
+        self.lcd.display_inversion_on(&mut self.delay)
+    }
+    /// Leave display inversion mode
+    pub fn display_inversion_off(&mut self) {
+        self.lcd.display_inversion_off(&mut self.delay)
+    }
+    /// Recover from DISPLAY OFF mode
+    pub fn display_on(&mut self) {
+        self.lcd.display_on(&mut self.delay)
+    }
+    /// Ramps the backlight from `from` to `to`; see [`Lcd::fade_backlight`]
+    pub fn fade_backlight(
+        &mut self,
+        from: u8,
+        to: u8,
+        steps: u16,
+        duration_ms: u32,
+    ) -> Result<(), DriverError<SPI, RST, WR, PWR>> {
+        self.lcd
+            .fade_backlight(from, to, steps, duration_ms, &mut self.delay)
+    }
+}
+
+impl<SPI, RST, WR, PWR, D> Deref for LcdWithDelay<SPI, RST, WR, PWR, D> {
+    type Target = Lcd<SPI, RST, WR, PWR>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.lcd
+    }
+}
+
+impl<SPI, RST, WR, PWR, D> DerefMut for LcdWithDelay<SPI, RST, WR, PWR, D> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.lcd
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use super::*;
+    use crate::interface::NoResetPin;
+    use core::cell::RefCell;
+    use display_interface::{DataFormat, DisplayError};
+    use embedded_graphics::pixelcolor::raw::RawU16;
+    use embedded_graphics::primitives::{PrimitiveStyle, Rectangle};
+    use embedded_hal_mock::eh1::delay::NoopDelay;
+    use embedded_hal_mock::eh1::digital::{Mock as PinMock, State, Transaction as PinTransaction};
+    use embedded_hal_mock::eh1::spi::{Mock as SpiMock, Transaction as SpiTransaction};
+    use std::vec::Vec;
+
+    /// Expected bus traffic: SPI writes and the DC level set before each of them
+    #[derive(Default)]
+    struct Expected {
+        spi: Vec<SpiTransaction<u8>>,
+        dc: Vec<PinTransaction>,
+    }
+
+    impl Expected {
+        /// A flushed write with DC at `dc`
+        fn write(&mut self, dc: State, bytes: &[u8]) -> &mut Self {
+            self.dc.push(PinTransaction::set(dc));
+            self.spi.push(SpiTransaction::write_vec(bytes.to_vec()));
+            self.spi.push(SpiTransaction::flush());
+            self
+        }
+        /// A command byte followed by its parameters, if any
+        fn command(&mut self, cmd: Cmd, params: &[u8]) -> &mut Self {
+            self.write(State::Low, &[cmd.bits()]);
+            if !params.is_empty() {
+                self.write(State::High, params);
+            }
+            self
+        }
+        /// Number of SPI writes expected
+        fn writes(&self) -> usize {
+            self.spi.len() / 2
+        }
+    }
+
+    type MockLcd = Lcd<SpiMock<u8>, PinMock, PinMock, PinMock>;
+
+    /// Runs `f` on an `Lcd` backed by mocks that expect exactly `expected`
+    fn with_lcd(expected: &Expected, f: impl FnOnce(&mut MockLcd)) {
+        let mut spi = SpiMock::new(&expected.spi);
+        let mut rst = PinMock::new(&[]);
+        let mut dc = PinMock::new(&expected.dc);
+        let mut pwr = PinMock::new(&[]);
+        let mut lcd = Lcd {
+            interface: DisplayInterface::new(spi.clone(), rst.clone(), dc.clone(), pwr.clone()),
+            madctl: 0x00,
+            geometry: Geometry::PORTRAIT,
+            byte_order: ByteOrder::default(),
+            pixel_format: None,
+            scroll_area: (0, ROWS),
+            scroll_offset: 0,
+            command_timing: InitTiming::default(),
+            backlight_pending: false,
+            inversion: true,
+        };
+        f(&mut lcd);
+        spi.done();
+        rst.done();
+        dc.done();
+        pwr.done();
+    }
+
+    #[test]
+    fn filled_rectangle_is_sent_as_one_burst_per_row() {
+        let color = Rgb565::RED.into_storage();
+        let row = [(color >> 8) as u8, color as u8].repeat(4);
+        let mut expected = Expected::default();
+        for y in 10..13 {
+            expected
+                .command(Cmd::CASET, &[0, 20, 0, 23])
+                .command(Cmd::RASET, &[0, y, 0, y])
+                .command(Cmd::RAMWR, &row);
+        }
+        // Pixel-by-pixel drawing would need six writes for each of the 12 pixels
+        assert!(expected.writes() < 12 * 6);
+        with_lcd(&expected, |lcd| {
+            Rectangle::new(Point::new(20, 10), Size::new(4, 3))
+                .into_styled(PrimitiveStyle::with_fill(Rgb565::RED))
+                .draw(lcd)
+                .unwrap();
+        });
+    }
+
+    #[test]
+    fn init_sends_bring_up_sequence() {
+        let mut expected = Expected::default();
+        expected
+            .command(Cmd::SLPOUT, &[])
+            .command(Cmd::COLMOD, &[0x65])
+            .command(Cmd::VCOMS, &[DEFAULT_VCOMS])
+            .command(Cmd::VDVVRHEN, &[0x01, 0xFF])
+            .command(Cmd::VRHS, &[DEFAULT_VRHS])
+            .command(Cmd::VDVS, &[DEFAULT_VDVS])
+            .command(Cmd::PWCTRL1, &DEFAULT_PWCTRL1)
+            .command(Cmd::INVON, &[])
+            .command(Cmd::DISPON, &[])
+            .command(Cmd::MADCTL, &[0x00]);
+        let mut spi = SpiMock::new(&expected.spi);
+        let mut rst = PinMock::new(&[
+            PinTransaction::set(State::Low),
+            PinTransaction::set(State::High),
+        ]);
+        let mut dc = PinMock::new(&expected.dc);
+        let mut pwr = PinMock::new(&[PinTransaction::set(State::High)]);
+
+        Lcd::init(
+            spi.clone(),
+            rst.clone(),
+            dc.clone(),
+            pwr.clone(),
+            &mut NoopDelay,
+        );
+
+        spi.done();
+        rst.done();
+        dc.done();
+        pwr.done();
+    }
+
+    #[test]
+    fn deferred_backlight_turns_on_after_the_first_flush() {
+        let frame = Frame([0x5A; 16]);
+        let mut expected = Expected::default();
+        expected
+            .command(Cmd::SLPOUT, &[])
+            .command(Cmd::COLMOD, &[0x65])
+            .command(Cmd::VCOMS, &[DEFAULT_VCOMS])
+            .command(Cmd::VDVVRHEN, &[0x01, 0xFF])
+            .command(Cmd::VRHS, &[DEFAULT_VRHS])
+            .command(Cmd::VDVS, &[DEFAULT_VDVS])
+            .command(Cmd::PWCTRL1, &DEFAULT_PWCTRL1)
+            .command(Cmd::INVON, &[])
+            .command(Cmd::DISPON, &[])
+            .command(Cmd::MADCTL, &[0x00]);
+        for _ in 0..2 {
+            expected
+                .command(Cmd::CASET, &[0, 10, 0, 11])
+                .command(Cmd::RASET, &[0, 20, 0, 23])
+                .command(Cmd::RAMWR, &frame.0);
+        }
+        let mut spi = SpiMock::new(&expected.spi);
+        let mut rst = PinMock::new(&[
+            PinTransaction::set(State::Low),
+            PinTransaction::set(State::High),
+        ]);
+        let mut dc = PinMock::new(&expected.dc);
+        // Off during init, on once after the first flush
+        let mut pwr = PinMock::new(&[
+            PinTransaction::set(State::Low),
+            PinTransaction::set(State::High),
+        ]);
+
+        let options = InitOptions {
+            defer_backlight: true,
+            ..InitOptions::default()
+        };
+        let mut lcd = Lcd::init_with_options(
+            spi.clone(),
+            rst.clone(),
+            dc.clone(),
+            pwr.clone(),
+            &mut NoopDelay,
+            options,
+        );
+        lcd.flush(&frame).unwrap();
+        lcd.flush(&frame).unwrap();
+
+        spi.done();
+        rst.done();
+        dc.done();
+        pwr.done();
+    }
+
+    /// Records every transfer as `(is_data, bytes)`
+    struct RecordingDi<'a>(&'a RefCell<Vec<(bool, Vec<u8>)>>);
+
+    impl WriteOnlyDataCommand for RecordingDi<'_> {
+        fn send_commands(&mut self, cmd: DataFormat<'_>) -> Result<(), DisplayError> {
+            let DataFormat::U8(bytes) = cmd else {
+                return Err(DisplayError::DataFormatNotImplemented);
+            };
+            self.0.borrow_mut().push((false, bytes.to_vec()));
+            Ok(())
+        }
+        fn send_data(&mut self, buf: DataFormat<'_>) -> Result<(), DisplayError> {
+            let DataFormat::U8(bytes) = buf else {
+                return Err(DisplayError::DataFormatNotImplemented);
+            };
+            self.0.borrow_mut().push((true, bytes.to_vec()));
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn init_with_interface_routes_commands_and_data() {
+        let log = RefCell::new(Vec::new());
+        let mut pwr = PinMock::new(&[PinTransaction::set(State::High)]);
+        let mut lcd = Lcd::init_with_interface(
+            RecordingDi(&log),
+            NoResetPin::new(),
+            pwr.clone(),
+            &mut NoopDelay,
+        );
+        assert_eq!(log.borrow()[0], (false, [Cmd::SWRESET.bits()].to_vec()));
+        assert_eq!(log.borrow()[1], (false, [Cmd::SLPOUT.bits()].to_vec()));
+        assert_eq!(log.borrow()[2], (false, [Cmd::COLMOD.bits()].to_vec()));
+        assert_eq!(log.borrow()[3], (true, [0x65].to_vec()));
+
+        // A second panel keeps its own data/command state while both are in use
+        let other_log = RefCell::new(Vec::new());
+        let mut other_pwr = PinMock::new(&[PinTransaction::set(State::Low)]);
+        let options = InitOptions {
+            defer_backlight: true,
+            ..InitOptions::default()
+        };
+        let mut other = Lcd::from_di(
+            RecordingDi(&other_log),
+            NoResetPin::new(),
+            other_pwr.clone(),
+            &mut NoopDelay,
+            options,
+        );
+        log.borrow_mut().clear();
+        other_log.borrow_mut().clear();
+        other.backlight_on_after_first_flush(false);
+        lcd.interface.data_async(&[0x12]).unwrap();
+        other.interface.cmd_async(Cmd::RAMWR.bits()).unwrap();
+        lcd.interface.data_continue(&[0x34]).unwrap();
+        assert_eq!(
+            *log.borrow(),
+            [(true, [0x12].to_vec()), (true, [0x34].to_vec())]
+        );
+        assert_eq!(*other_log.borrow(), [(false, [Cmd::RAMWR.bits()].to_vec())]);
+
+        // Frames reach `send_data` as one slice
+        other_log.borrow_mut().clear();
+        other.flush(&Frame([0x5A; 16])).unwrap();
+        assert_eq!(other_log.borrow()[5], (true, [0x5A; 16].to_vec()));
+        assert!(lcd.read_madctl().is_err());
+        pwr.done();
+        other_pwr.done();
+    }
+
+    #[test]
+    fn batched_frame_area_is_four_interface_transactions() {
+        let log = RefCell::new(Vec::new());
+        let mut pwr = PinMock::new(&[PinTransaction::set(State::High)]);
+        let mut lcd = Lcd::init_with_interface(
+            RecordingDi(&log),
+            NoResetPin::new(),
+            pwr.clone(),
+            &mut NoopDelay,
+        );
+        log.borrow_mut().clear();
+        lcd.set_frame_area_batched(300, 10, 319, 239).unwrap();
+        assert_eq!(
+            *log.borrow(),
+            [
+                (false, [Cmd::CASET.bits()].to_vec()),
+                (true, [0x01, 0x2C, 0x01, 0x3F].to_vec()),
+                (false, [Cmd::RASET.bits()].to_vec()),
+                (true, [0x00, 0x0A, 0x00, 0xEF].to_vec()),
+            ]
+        );
+        pwr.done();
+    }
+
+    #[test]
+    fn frame_area_coordinates_are_sent_big_endian() {
+        let cases = [
+            (
+                (0, 0, 0, 0),
+                [0x00, 0x00, 0x00, 0x00],
+                [0x00, 0x00, 0x00, 0x00],
+            ),
+            (
+                (10, 20, 239, 319),
+                [0x00, 0x0A, 0x00, 0xEF],
+                [0x00, 0x14, 0x01, 0x3F],
+            ),
+            (
+                (300, 256, 319, 511),
+                [0x01, 0x2C, 0x01, 0x3F],
+                [0x01, 0x00, 0x01, 0xFF],
+            ),
+        ];
+        for ((start_x, start_y, end_x, end_y), caset, raset) in cases {
+            let mut expected = Expected::default();
+            expected
+                .command(Cmd::CASET, &caset)
+                .command(Cmd::RASET, &raset);
+            with_lcd(&expected, |lcd| {
+                lcd.set_frame_area(start_x, start_y, end_x, end_y).unwrap();
+            });
+        }
+    }
+
+    #[test]
+    fn landscape_columns_above_255_keep_their_high_byte() {
+        let color = Rgb565::GREEN.into_storage().to_be_bytes();
+        let mut expected = Expected::default();
+        expected
+            .command(Cmd::MADCTL, &[0x20])
+            .command(Cmd::CASET, &[0x01, 0x3F, 0x01, 0x3F])
+            .command(Cmd::RASET, &[0x00, 0xEF, 0x00, 0xEF])
+            .command(Cmd::RAMWR, &color)
+            .command(Cmd::CASET, &[0x01, 0x00, 0x01, 0x3F]);
+        with_lcd(&expected, |lcd| {
+            lcd.set_geometry(Geometry::LANDSCAPE).unwrap();
+            lcd.set_pixel(319, 239, Rgb565::GREEN.into_storage())
+                .unwrap();
+            lcd.set_columns(256, 319).unwrap();
+        });
+    }
+
+    #[test]
+    fn set_pixel_sets_window_then_writes_color() {
+        let mut expected = Expected::default();
+        expected
+            .command(Cmd::CASET, &[0x00, 0xC8, 0x00, 0xC8])
+            .command(Cmd::RASET, &[0x01, 0x2C, 0x01, 0x2C])
+            .command(Cmd::RAMWR, &[0xF8, 0x1F]);
+        with_lcd(&expected, |lcd| lcd.set_pixel(200, 300, 0xF81F).unwrap());
+    }
+
+    #[test]
+    fn scroll_text_up_returns_exposed_rows_across_wraparound() {
+        let mut expected = Expected::default();
+        expected
+            .command(Cmd::VSCRDEF, &[0, 20, 1, 0x18, 0, 20])
+            .command(Cmd::VSCRSADD, &[0, 20])
+            .command(Cmd::VSCRSADD, &[0x01, 0x0E])
+            .command(Cmd::VSCRSADD, &[0, 40]);
+        with_lcd(&expected, |lcd| {
+            lcd.set_scroll_area(20, 280, 20).unwrap();
+            assert_eq!(lcd.scroll_text_up(250).unwrap(), [20..270, 20..20]);
+            assert_eq!(lcd.scroll_text_up(50).unwrap(), [270..300, 20..40]);
+        });
+    }
+
+    #[test]
+    fn recover_reinitializes_and_restores_tracked_state() {
+        let mut expected = Expected::default();
+        expected
+            .command(Cmd::COLMOD, &[COLMOD_RGB444])
+            .command(Cmd::MADCTL, &[0x40])
+            .command(Cmd::SWRESET, &[]);
+        for step in DEFAULT_INIT {
+            expected.write(State::Low, &[step.cmd]);
+            if !step.data.is_empty() {
+                expected.write(State::High, step.data);
+            }
+        }
+        expected
+            .command(Cmd::MADCTL, &[0x40])
+            .command(Cmd::COLMOD, &[COLMOD_RGB444]);
+        with_lcd(&expected, |lcd| {
+            lcd.set_pixel_format(PixelFormat::Rgb444);
+            lcd.set_mirror_x(true).unwrap();
+            lcd.recover(&AtkMd0240, &mut NoopDelay).unwrap();
+            assert_eq!(lcd.pixel_format(), Some(PixelFormat::Rgb444));
+        });
+    }
+
+    #[test]
+    fn recover_replays_the_profile_with_inversion_off() {
+        struct Minimal;
+
+        const STEPS: &[InitStep<'static>] = &[
+            InitStep {
+                cmd: Cmd::INVON.bits(),
+                data: &[],
+                delay_ms: 0,
+            },
+            InitStep {
+                cmd: Cmd::DISPON.bits(),
+                data: &[],
+                delay_ms: 0,
+            },
+        ];
+
+        impl PanelProfile for Minimal {
+            fn init_steps(&self) -> &[InitStep<'_>] {
+                STEPS
+            }
+        }
+
+        let mut expected = Expected::default();
+        expected
+            .command(Cmd::INVOFF, &[])
+            .command(Cmd::SWRESET, &[])
+            .command(Cmd::INVOFF, &[])
+            .command(Cmd::DISPON, &[])
+            .command(Cmd::MADCTL, &[0x00])
+            .command(Cmd::COLMOD, &[COLMOD_RGB565]);
+        with_lcd(&expected, |lcd| {
+            lcd.pixel_format = Some(PixelFormat::Rgb565);
+            lcd.display_inversion_off(&mut NoopDelay);
+            lcd.recover(&Minimal, &mut NoopDelay).unwrap();
+        });
+    }
+
+    #[test]
+    fn init_with_profile_runs_its_steps_and_defaults() {
+        struct Bgr444;
+
+        impl PanelProfile for Bgr444 {
+            fn init_steps(&self) -> &[InitStep<'_>] {
+                &GENERIC_ST7789_INIT[..1]
+            }
+            fn madctl(&self) -> Madctl {
+                Madctl::MX | Madctl::BGR
+            }
+            fn pixel_format(&self) -> PixelFormat {
+                PixelFormat::Rgb444
+            }
+        }
+
+        let mut expected = Expected::default();
+        expected
+            .command(Cmd::SWRESET, &[])
+            .command(Cmd::SLPOUT, &[])
+            .command(Cmd::COLMOD, &[COLMOD_RGB444])
+            .command(Cmd::MADCTL, &[0x48]);
+        let mut spi = SpiMock::new(&expected.spi);
+        let mut dc = PinMock::new(&expected.dc);
+        let mut pwr = PinMock::new(&[PinTransaction::set(State::High)]);
+        let options = InitOptions::default();
+        let lcd = Lcd::init_with_profile(
+            spi.clone(),
+            NoResetPin::new(),
+            dc.clone(),
+            pwr.clone(),
+            &mut NoopDelay,
+            &Bgr444,
+            options,
+        );
+        assert_eq!(lcd.pixel_format(), Some(PixelFormat::Rgb444));
+        spi.done();
+        dc.done();
+        pwr.done();
+    }
+
+    #[test]
+    fn reset_without_reset_pin_sends_swreset() {
+        let mut expected = Expected::default();
+        expected
+            .command(Cmd::SWRESET, &[])
+            .command(Cmd::SLPOUT, &[]);
+        let mut spi = SpiMock::new(&expected.spi);
+        let mut dc = PinMock::new(&expected.dc);
+        let mut pwr = PinMock::new(&[]);
+        let mut interface =
+            DisplayInterface::new(spi.clone(), NoResetPin::new(), dc.clone(), pwr.clone());
+        interface.reset(&mut NoopDelay);
+        interface.cmd(Cmd::SLPOUT.bits()).unwrap();
+        spi.done();
+        dc.done();
+        pwr.done();
+    }
+
+    #[test]
+    fn backlight_errors_keep_the_hal_error() {
+        use embedded_hal_mock::eh1::MockError;
+
+        let error = MockError::Io(std::io::ErrorKind::BrokenPipe);
+        let mut spi = SpiMock::new(&[]);
+        let mut dc = PinMock::new(&[]);
+        let mut pwr = PinMock::new(&[PinTransaction::set(State::High).with_error(error.clone())]);
+        // The reset pin's `Infallible` need not match the DC pin's `MockError`
+        let mut interface =
+            DisplayInterface::new(spi.clone(), NoResetPin::new(), dc.clone(), pwr.clone());
+        assert_eq!(interface.set_backlight(255), Err(Error::Backlight(error)));
+        spi.done();
+        dc.done();
+        pwr.done();
+    }
+
+    #[test]
+    fn set_pixel_ignores_off_panel_coordinates() {
+        with_lcd(&Expected::default(), |lcd| {
+            lcd.set_pixel(COLS, 0, 0xFFFF).unwrap();
+            lcd.set_pixel(0, ROWS, 0xFFFF).unwrap();
+            lcd.set_pixel(u16::MAX, u16::MAX, 0xFFFF).unwrap();
+        });
+    }
+
+    #[test]
+    fn data_is_split_at_max_transfer() {
+        let data: Vec<u8> = (0..10).collect();
+        let mut expected = Expected::default();
+        expected.command(Cmd::RAMWR, &[]);
+        expected.dc.push(PinTransaction::set(State::High));
+        for chunk in data.chunks(4) {
+            expected.spi.push(SpiTransaction::write_vec(chunk.to_vec()));
+        }
+        expected.spi.push(SpiTransaction::flush());
+        with_lcd(&expected, |lcd| {
+            lcd.set_max_transfer(4);
+            lcd.interface
+                .write_command(Cmd::RAMWR.bits(), &data)
+                .unwrap();
+            #[cfg(feature = "stats")]
+            assert_eq!(
+                lcd.take_stats(),
+                FlushStats {
+                    bytes: 11,
+                    transactions: 4,
+                }
+            );
+        });
+    }
+
+    #[test]
+    fn flush_to_all_sends_the_buffer_to_every_panel() {
+        let frame = Frame([0x5A; 16]);
+        let mut expected = Expected::default();
+        expected
+            .command(Cmd::CASET, &[0, 10, 0, 11])
+            .command(Cmd::RASET, &[0, 20, 0, 23])
+            .command(Cmd::RAMWR, &frame.0);
+        with_lcd(&expected, |first| {
+            with_lcd(&expected, |second| {
+                Lcd::flush_to_all(&mut [first, second], &frame).unwrap();
+            })
+        });
+    }
+
+    #[test]
+    fn sync_flushes_pending_data_before_driving_dc_low() {
+        let mut expected = Expected::default();
+        expected.dc.push(PinTransaction::set(State::High));
+        expected
+            .spi
+            .push(SpiTransaction::write_vec([0x12, 0x34].to_vec()));
+        expected.spi.push(SpiTransaction::flush());
+        expected.write(State::Low, &[Cmd::NOP.bits()]);
+        with_lcd(&expected, |lcd| {
+            lcd.append_data_nowait(&[0x12, 0x34]).unwrap();
+            lcd.sync().unwrap();
+        });
+    }
+
+    #[test]
+    fn batched_script_flushes_before_every_dc_change() {
+        let script = [
+            InitStep {
+                cmd: Cmd::SLPOUT.bits(),
+                data: &[],
+                delay_ms: 0,
+            },
+            InitStep {
+                cmd: Cmd::INVON.bits(),
+                data: &[],
+                delay_ms: 0,
+            },
+            InitStep {
+                cmd: Cmd::COLMOD.bits(),
+                data: &[0x55],
+                delay_ms: 0,
+            },
+            InitStep {
+                cmd: Cmd::DISPON.bits(),
+                data: &[],
+                delay_ms: 0,
+            },
+        ];
+        let mut expected = Expected::default();
+        for cmd in [Cmd::SLPOUT, Cmd::INVON, Cmd::COLMOD] {
+            expected.dc.push(PinTransaction::set(State::Low));
+            expected
+                .spi
+                .push(SpiTransaction::write_vec([cmd.bits()].to_vec()));
+        }
+        expected.spi.push(SpiTransaction::flush());
+        expected.write(State::High, &[0x55]);
+        expected.write(State::Low, &[Cmd::DISPON.bits()]);
+        with_lcd(&expected, |lcd| {
+            lcd.run_init_script_batched(&script, &mut NoopDelay)
+                .unwrap()
+        });
+    }
+
+    #[test]
+    fn write_session_drives_dc_high_once_per_ramwr() {
+        let mut expected = Expected::default();
+        expected
+            .command(Cmd::CASET, &[0, 0, 0, 1])
+            .command(Cmd::RASET, &[0, 0, 0, 1])
+            .write(State::Low, &[Cmd::RAMWR.bits()]);
+        expected.dc.push(PinTransaction::set(State::High));
+        for _ in 0..4 {
+            expected
+                .spi
+                .push(SpiTransaction::write_vec([0x12, 0x34].to_vec()));
+        }
+        expected.spi.push(SpiTransaction::flush());
+        with_lcd(&expected, |lcd| {
+            let mut session = lcd.begin_write(0, 0, 1, 1).unwrap();
+            for _ in 0..4 {
+                session.push(0x1234).unwrap();
+            }
+            session.end().unwrap();
+        });
+    }
+
+    #[test]
+    fn write_colors_streams_in_stack_chunks() {
+        let colors = (0..70u16).map(|i| Rgb565::from(RawU16::new(i)));
+        let bytes: Vec<u8> = (0..70u16).flat_map(u16::to_be_bytes).collect();
+        let mut expected = Expected::default();
+        // DC is driven high once for the whole stream
+        expected.dc.push(PinTransaction::set(State::High));
+        for chunk in bytes.chunks(COLOR_CHUNK_PIXELS * 2) {
+            expected.spi.push(SpiTransaction::write_vec(chunk.to_vec()));
+        }
+        expected.spi.push(SpiTransaction::flush());
+        with_lcd(&expected, |lcd| lcd.write_colors(colors).unwrap());
+    }
+
+    #[test]
+    #[cfg(feature = "stack_alloc")]
+    fn flush_rotated_streams_pixels_in_rotated_order() {
+        let mut display = Display2in14::<{ LINE_SIZE * 2 }>::new_strip(Rgb565::BLACK);
+        display
+            .draw_iter([
+                Pixel(Point::new(0, 0), Rgb565::RED),
+                Pixel(Point::new(239, 1), Rgb565::BLUE),
+            ])
+            .unwrap();
+        // Rotate90 shows the 240x2 strip as a 2x240 column: buffer (239, 1) lands at (1, 0)
+        // and buffer (0, 0) at (0, 239)
+        let mut bytes = [0u8; LINE_SIZE * 2];
+        bytes[2..4].copy_from_slice(&Rgb565::BLUE.into_storage().to_be_bytes());
+        bytes[956..958].copy_from_slice(&Rgb565::RED.into_storage().to_be_bytes());
+        let mut expected = Expected::default();
+        expected
+            .command(Cmd::CASET, &[0, 0, 0, 1])
+            .command(Cmd::RASET, &[0, 0, 0, 239])
+            .write(State::Low, &[Cmd::RAMWR.bits()]);
+        expected.dc.push(PinTransaction::set(State::High));
+        for chunk in bytes.chunks(COLOR_CHUNK_PIXELS * 2) {
+            expected.spi.push(SpiTransaction::write_vec(chunk.to_vec()));
+        }
+        expected.spi.push(SpiTransaction::flush());
+        with_lcd(&expected, |lcd| {
+            lcd.flush_rotated(&display, DisplayRotation::Rotate90)
+                .unwrap();
+            // A full portrait frame turned sideways is 320 pixels wide
+            let frame = Display2in14::<FRAME_SIZE>::new(Rgb565::BLACK);
+            assert_eq!(
+                lcd.flush_rotated(&frame, DisplayRotation::Rotate270),
+                Err(Error::OutOfBounds)
+            );
+        });
+    }
+
+    #[test]
+    #[cfg(feature = "stack_alloc")]
+    fn flush_packed_converts_while_streaming() {
+        use embedded_graphics::pixelcolor::BinaryColor;
+
+        let mut display = Display2in14Packed::<BinaryColor, 30>::new(BinaryColor::Off);
+        display
+            .draw_iter([Pixel(Point::new(2, 0), BinaryColor::On)])
+            .unwrap();
+        let mut bytes = [0u8; LINE_SIZE];
+        bytes[4..6].copy_from_slice(&[0xFF, 0xFF]);
+        let mut expected = Expected::default();
+        expected
+            .command(Cmd::CASET, &[0, 0, 0, 239])
+            .command(Cmd::RASET, &[0, 0, 0, 0])
+            .write(State::Low, &[Cmd::RAMWR.bits()]);
+        expected.dc.push(PinTransaction::set(State::High));
+        for chunk in bytes.chunks(COLOR_CHUNK_PIXELS * 2) {
+            expected.spi.push(SpiTransaction::write_vec(chunk.to_vec()));
+        }
+        expected.spi.push(SpiTransaction::flush());
+        with_lcd(&expected, |lcd| lcd.flush_packed(&display).unwrap());
+    }
+
+    #[test]
+    #[cfg(feature = "stack_alloc")]
+    fn flush_packed_rejects_another_geometry() {
+        use embedded_graphics::pixelcolor::BinaryColor;
+
+        let mut display = Display2in14Packed::<BinaryColor, 120>::new(BinaryColor::Off);
+        display.set_geometry(Geometry::LANDSCAPE);
+        with_lcd(&Expected::default(), |lcd| {
+            assert!(matches!(
+                lcd.flush_packed(&display),
+                Err(Error::OutOfBounds)
+            ));
+        });
+    }
+
+    #[test]
+    fn timings_are_applied_and_read_back_together() {
+        let timings = Timings {
+            reset: ResetTiming {
+                pulse_us: 50,
+                wait_ms: 200,
+            },
+            commands: InitTiming {
+                sleep_out_ms: 150,
+                inversion_ms: 5,
+                display_on_ms: 20,
+            },
+            backlight_us: 100,
+        };
+        with_lcd(&Expected::default(), |lcd| {
+            assert_eq!(lcd.timings(), Timings::default());
+            lcd.set_timings(timings);
+            assert_eq!(lcd.timings(), timings);
+        });
+        let options = InitOptions::default().with_timings(timings);
+        assert_eq!(options.reset_timing, timings.reset);
+        assert_eq!(options.timing, timings.commands);
+        assert!(options.inversion);
+    }
+
+    /// Framebuffer two pixels wide and four rows high at (10, 20)
+    struct Frame([u8; 16]);
+
+    impl Framebuffer for Frame {
+        fn bytes(&self) -> &[u8] {
+            &self.0
+        }
+        fn area(&self) -> (u16, u16, u16, u16) {
+            (10, 20, 11, 23)
+        }
+    }
+
+    #[test]
+    fn flush_with_progress_reports_each_chunk() {
+        let frame = Frame(core::array::from_fn(|i| i as u8));
+        let mut expected = Expected::default();
+        expected
+            .command(Cmd::CASET, &[0, 10, 0, 11])
+            .command(Cmd::RASET, &[0, 20, 0, 23])
+            .write(State::Low, &[Cmd::RAMWR.bits()]);
+        for chunk in frame.0.chunks(6) {
+            expected.write(State::High, chunk);
+        }
+        let mut progress = Vec::new();
+        with_lcd(&expected, |lcd| {
+            lcd.flush_with_progress(&frame, 6, |sent| progress.push(sent))
+                .unwrap();
+        });
+        assert_eq!(progress, [6, 12, 16]);
+    }
+
+    #[test]
+    fn interrupted_write_resumes_mid_row() {
+        let mut expected = Expected::default();
+        expected
+            .command(Cmd::CASET, &[0, 0, 0, 3])
+            .command(Cmd::RASET, &[0, 0, 0, 1])
+            .write(State::Low, &[Cmd::RAMWR.bits()]);
+        expected.dc.push(PinTransaction::set(State::High));
+        expected
+            .spi
+            .push(SpiTransaction::write_vec([1, 2, 3, 4, 5, 6].to_vec()));
+        expected.spi.push(SpiTransaction::flush());
+        expected
+            .write(State::Low, &[Cmd::NOP.bits()])
+            // The rest of the first row
+            .command(Cmd::CASET, &[0, 3, 0, 3])
+            .command(Cmd::RASET, &[0, 0, 0, 0])
+            .write(State::Low, &[Cmd::RAMWR.bits()]);
+        expected.dc.push(PinTransaction::set(State::High));
+        expected
+            .spi
+            .push(SpiTransaction::write_vec([7, 8].to_vec()));
+        expected.spi.push(SpiTransaction::flush());
+        expected
+            // The full-width window from the second row on
+            .command(Cmd::CASET, &[0, 0, 0, 3])
+            .command(Cmd::RASET, &[0, 1, 0, 1])
+            .write(State::Low, &[Cmd::RAMWR.bits()]);
+        expected.dc.push(PinTransaction::set(State::High));
+        expected
+            .spi
+            .push(SpiTransaction::write_vec([9, 10].to_vec()));
+        expected.spi.push(SpiTransaction::flush());
+        with_lcd(&expected, |lcd| {
+            let mut session = lcd.begin_write(0, 0, 3, 1).unwrap();
+            session.push_slice(&[1, 2, 3, 4, 5, 6]).unwrap();
+            session.with_interruption(|lcd| lcd.nop()).unwrap();
+            session.push_slice(&[7, 8, 9, 10]).unwrap();
+            session.end().unwrap();
+        });
+    }
+
+    #[test]
+    fn failed_interruption_still_resumes_the_write() {
+        use embedded_hal_mock::eh1::MockError;
+
+        let mut expected = Expected::default();
+        expected
+            .command(Cmd::CASET, &[0, 0, 0, 3])
+            .command(Cmd::RASET, &[0, 0, 0, 1])
+            .write(State::Low, &[Cmd::RAMWR.bits()]);
+        expected.dc.push(PinTransaction::set(State::High));
+        expected
+            .spi
+            .push(SpiTransaction::write_vec([1, 2].to_vec()));
+        expected.spi.push(SpiTransaction::flush());
+        expected.write(State::Low, &[Cmd::NOP.bits()]);
+        // Resuming fails on the first attempt ...
+        expected.dc.push(
+            PinTransaction::set(State::Low)
+                .with_error(MockError::Io(std::io::ErrorKind::BrokenPipe)),
+        );
+        // ... and is retried by the next push, before any pixel data is sent
+        expected
+            .command(Cmd::CASET, &[0, 1, 0, 3])
+            .command(Cmd::RASET, &[0, 0, 0, 0])
+            .write(State::Low, &[Cmd::RAMWR.bits()]);
+        expected.dc.push(PinTransaction::set(State::High));
+        expected
+            .spi
+            .push(SpiTransaction::write_vec([3, 4].to_vec()));
+        expected.spi.push(SpiTransaction::flush());
+        with_lcd(&expected, |lcd| {
+            let mut session = lcd.begin_write(0, 0, 3, 1).unwrap();
+            session.push_slice(&[1, 2]).unwrap();
+            let result = session.with_interruption(|lcd| {
+                lcd.nop()?;
+                Err::<(), _>(Error::OutOfBounds)
+            });
+            assert!(matches!(result, Err(Error::Pin(_))));
+            session.push_slice(&[3, 4]).unwrap();
+            session.end().unwrap();
+        });
+    }
+
+    #[test]
+    fn interruption_error_is_returned_after_resuming() {
+        let mut expected = Expected::default();
+        expected
+            .command(Cmd::CASET, &[0, 0, 0, 3])
+            .command(Cmd::RASET, &[0, 0, 0, 1])
+            .write(State::Low, &[Cmd::RAMWR.bits()]);
+        expected.spi.push(SpiTransaction::flush());
+        expected
+            .command(Cmd::CASET, &[0, 0, 0, 3])
+            .command(Cmd::RASET, &[0, 0, 0, 1])
+            .write(State::Low, &[Cmd::RAMWR.bits()]);
+        expected.dc.push(PinTransaction::set(State::High));
+        expected
+            .spi
+            .push(SpiTransaction::write_vec([1, 2].to_vec()));
+        expected.spi.push(SpiTransaction::flush());
+        with_lcd(&expected, |lcd| {
+            let mut session = lcd.begin_write(0, 0, 3, 1).unwrap();
+            let result = session.with_interruption(|_| Err::<(), _>(Error::OutOfBounds));
+            assert!(matches!(result, Err(Error::OutOfBounds)));
+            session.push_slice(&[1, 2]).unwrap();
+            session.end().unwrap();
+        });
+    }
+
+    /// Two-byte framebuffer covering the top-left pixel
+    struct OnePixel;
+
+    impl Framebuffer for OnePixel {
+        fn bytes(&self) -> &[u8] {
+            &[0xF8, 0x00]
+        }
+        fn area(&self) -> (u16, u16, u16, u16) {
+            (0, 0, 0, 0)
+        }
+    }
+
+    #[test]
+    fn flush_synced_waits_for_rising_te_edge() {
+        let mut expected = Expected::default();
+        expected
+            .command(Cmd::CASET, &[0, 0, 0, 0])
+            .command(Cmd::RASET, &[0, 0, 0, 0])
+            .command(Cmd::RAMWR, &[0xF8, 0x00]);
+        // Mid-blanking at first, so the next blanking period is awaited
+        let mut te = PinMock::new(&[
+            PinTransaction::get(State::High),
+            PinTransaction::get(State::Low),
+            PinTransaction::get(State::Low),
+            PinTransaction::get(State::High),
+        ]);
+        with_lcd(&expected, |lcd| {
+            lcd.flush_synced(&OnePixel, &mut te, &mut NoopDelay, 100)
+                .unwrap();
+        });
+        te.done();
+    }
+
+    #[test]
+    fn flush_synced_times_out_without_te() {
+        // One read finds TE low, four more poll for the edge until the 3 µs budget runs out
+        let mut te = PinMock::new(&[(); 5].map(|_| PinTransaction::get(State::Low)));
+        with_lcd(&Expected::default(), |lcd| {
+            let result = lcd.flush_synced(&OnePixel, &mut te, &mut NoopDelay, 3);
+            assert_eq!(result, Err(Error::Timeout));
+        });
+        te.done();
+    }
+
+    #[test]
+    fn flush_dirty_synced_sends_only_the_given_rows() {
+        let mut expected = Expected::default();
+        expected
+            .command(Cmd::CASET, &[0, 10, 0, 11])
+            .command(Cmd::RASET, &[0, 21, 0, 22])
+            .command(Cmd::RAMWR, &[1, 2, 3, 4, 5, 6, 7, 8]);
+        let mut te = PinMock::new(&[
+            PinTransaction::get(State::Low),
+            PinTransaction::get(State::High),
+        ]);
+        let mut frame = Frame([0; 16]);
+        frame.0[4..12].copy_from_slice(&[1, 2, 3, 4, 5, 6, 7, 8]);
+        with_lcd(&expected, |lcd| {
+            lcd.flush_dirty_synced(&frame, 1..3, &mut te, &mut NoopDelay, 100)
+                .unwrap();
+            // Nothing dirty: no wait, no traffic
+            lcd.flush_dirty_synced(&frame, 4..9, &mut te, &mut NoopDelay, 100)
+                .unwrap();
+        });
+        te.done();
+    }
+
+    #[test]
+    fn self_diagnostic_reads_one_byte_without_dummy() {
+        let mut expected = Expected::default();
+        expected.command(Cmd::RDDSDR, &[]);
+        expected.dc.push(PinTransaction::set(State::High));
+        expected.spi.push(SpiTransaction::read(0xC5));
+        expected.spi.push(SpiTransaction::flush());
+        with_lcd(&expected, |lcd| {
+            assert_eq!(lcd.read_self_diagnostic().unwrap(), SelfDiagnostic::all());
+        });
+    }
+
+    /// Expects `detect_color_order` to write red at `(0, 0)` and read `response` back
+    fn color_order_read_back(response: [u8; 4]) -> Expected {
+        let mut expected = Expected::default();
+        expected
+            .command(Cmd::CASET, &[0, 0, 0, 0])
+            .command(Cmd::RASET, &[0, 0, 0, 0])
+            .command(Cmd::RAMWR, &[0xF8, 0x00])
+            .command(Cmd::RAMRD, &[]);
+        expected.dc.push(PinTransaction::set(State::High));
+        expected
+            .spi
+            .push(SpiTransaction::read_vec(response.to_vec()));
+        expected.spi.push(SpiTransaction::flush());
+        expected
+    }
+
+    #[test]
+    fn color_order_is_read_from_the_red_channel() {
+        with_lcd(&color_order_read_back([0x00, 0xF8, 0x00, 0x04]), |lcd| {
+            assert_eq!(lcd.detect_color_order().unwrap(), ColorOrder::Rgb);
+        });
+        with_lcd(&color_order_read_back([0x00, 0x00, 0x00, 0xFC]), |lcd| {
+            assert_eq!(lcd.detect_color_order().unwrap(), ColorOrder::Bgr);
+        });
+    }
+
+    #[test]
+    fn color_order_rejects_an_unconnected_miso() {
+        for response in [[0x00; 4], [0xFF; 4]] {
+            with_lcd(&color_order_read_back(response), |lcd| {
+                assert!(matches!(
+                    lcd.detect_color_order(),
+                    Err(Error::InvalidFormat)
+                ));
+            });
+        }
+    }
+
+    /// Three-byte RGB444 framebuffer covering the top-left two pixels
+    struct TwoPixels444;
+
+    impl Framebuffer for TwoPixels444 {
+        fn bytes(&self) -> &[u8] {
+            &[0xF0, 0x0F, 0x00]
+        }
+        fn area(&self) -> (u16, u16, u16, u16) {
+            (0, 0, 1, 0)
+        }
+        fn pixel_format(&self) -> PixelFormat {
+            PixelFormat::Rgb444
+        }
+    }
+
+    #[test]
+    fn pixel_format_follows_framebuffer() {
+        let mut expected = Expected::default();
+        expected
+            .command(Cmd::COLMOD, &[COLMOD_RGB444])
+            .command(Cmd::CASET, &[0, 0, 0, 1])
+            .command(Cmd::RASET, &[0, 0, 0, 0])
+            .command(Cmd::RAMWR, &[0xF0, 0x0F, 0x00]);
+        with_lcd(&expected, |lcd| {
+            lcd.set_pixel_format_for(&TwoPixels444).unwrap();
+            lcd.set_pixel_format_for(&TwoPixels444).unwrap();
+            lcd.flush(&TwoPixels444).unwrap();
+            assert_eq!(lcd.flush(&OnePixel), Err(Error::InvalidFormat));
+        });
+    }
+
+    #[test]
+    #[cfg(feature = "heap_alloc")]
+    fn frame_diff_sends_only_changed_row_runs() {
+        let mut frame = Frame([0; 16]);
+        let mut expected = Expected::default();
+        expected
+            .command(Cmd::CASET, &[0, 10, 0, 11])
+            .command(Cmd::RASET, &[0, 20, 0, 23])
+            .command(Cmd::RAMWR, &[0; 16])
+            .command(Cmd::CASET, &[0, 10, 0, 11])
+            .command(Cmd::RASET, &[0, 20, 0, 20])
+            .command(Cmd::RAMWR, &[1, 0, 0, 0])
+            .command(Cmd::CASET, &[0, 10, 0, 11])
+            .command(Cmd::RASET, &[0, 22, 0, 23])
+            .command(Cmd::RAMWR, &[0, 0, 0, 2, 3, 0, 0, 0]);
+        with_lcd(&expected, |lcd| {
+            let mut diff = FrameDiff::new();
+            diff.flush(lcd, &frame).unwrap();
+            diff.flush(lcd, &frame).unwrap();
+            frame.0[0] = 1;
+            frame.0[11] = 2;
+            frame.0[12] = 3;
+            diff.flush(lcd, &frame).unwrap();
+            diff.flush(lcd, &frame).unwrap();
+        });
+    }
+
+    #[test]
+    fn fade_backlight_ramps_the_pwm_duty_cycle() {
+        use crate::interface::PwmBacklight;
+        use embedded_hal_mock::eh1::pwm;
+
+        let expected: Vec<_> = [64, 127, 191, 255]
+            .into_iter()
+            .flat_map(|duty| {
+                [
+                    pwm::Transaction::max_duty_cycle(255),
+                    pwm::Transaction::set_duty_cycle(duty),
+                ]
+            })
+            .collect();
+        let mut channel = pwm::Mock::new(&expected);
+        let mut spi = SpiMock::new(&[]);
+        let mut dc = PinMock::new(&[]);
+        let mut interface = DisplayInterface::new(
+            spi.clone(),
+            NoResetPin::new(),
+            dc.clone(),
+            PwmBacklight(channel.clone()),
+        );
+        interface
+            .fade_backlight(64, 255, 3, 30, &mut NoopDelay)
+            .unwrap();
+        channel.done();
+        spi.done();
+        dc.done();
+    }
+
+    #[test]
+    fn fade_backlight_switches_a_gpio_backlight_at_once() {
+        let mut spi = SpiMock::new(&[]);
+        let mut dc = PinMock::new(&[]);
+        let mut pwr = PinMock::new(&[PinTransaction::set(State::High)]);
+        let mut interface =
+            DisplayInterface::new(spi.clone(), NoResetPin::new(), dc.clone(), pwr.clone());
+        interface
+            .fade_backlight(0, 200, 50, 500, &mut NoopDelay)
+            .unwrap();
+        spi.done();
+        dc.done();
+        pwr.done();
+    }
+
+    #[test]
+    fn hardware_rotation_replaces_scan_bits_only() {
+        let mut expected = Expected::default();
+        expected
+            .command(Cmd::MADCTL, &[0x08])
+            .command(Cmd::MADCTL, &[0x68])
+            .command(Cmd::MADCTL, &[0xC8]);
+        with_lcd(&expected, |lcd| {
+            lcd.set_bgr(true).unwrap();
+            lcd.set_hardware_rotation(DisplayRotation::Rotate90)
+                .unwrap();
+            assert_eq!(lcd.geometry(), Geometry::LANDSCAPE);
+            lcd.set_hardware_rotation(DisplayRotation::Rotate180)
+                .unwrap();
+            assert_eq!(lcd.geometry(), Geometry::PORTRAIT);
+        });
+    }
+}
@@ -0,0 +1,54 @@
+//! Driver error type
+use super::interface::{Backlight, ResetPin};
+use display_interface::DisplayError;
+use embedded_hal::{digital, spi};
+
+/// Errors returned by the LCD driver.
+///
+/// `PinE` is the error type of the data/command pin, `SpiE` that of the SPI bus, `RstE` that
+/// of the reset pin and `BlE` that of the backlight, so callers can tell which part failed and
+/// inspect the underlying HAL error. The pins may come from different HALs (a GPIO expander
+/// next to native pins, say), so their error types are independent.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Error<PinE, SpiE, RstE = PinE, BlE = PinE> {
+    /// The data/command pin failed
+    Pin(PinE),
+    /// An SPI transfer failed
+    Spi(SpiE),
+    /// The reset pin failed
+    Reset(RstE),
+    /// The backlight pin or PWM channel failed
+    Backlight(BlE),
+    /// Coordinates or an area fall outside the display
+    OutOfBounds,
+    /// Data has the wrong length or layout for the requested operation
+    InvalidFormat,
+    /// Reading the tearing-effect input pin failed; the pin is passed per call, so only its
+    /// error kind is kept
+    TearingEffect(digital::ErrorKind),
+    /// A signal the driver waited for did not arrive in time
+    Timeout,
+}
+
+/// [`Error`] raised by a driver using an `SPI` bus, a `RST` reset pin, a `WR` data/command pin
+/// and a `PWR` backlight
+pub type DriverError<SPI, RST, WR, PWR> = Error<
+    <WR as digital::ErrorType>::Error,
+    <SPI as spi::ErrorType>::Error,
+    <RST as ResetPin>::Error,
+    <PWR as Backlight>::Error,
+>;
+
+/// Collapses the error into the `display-interface` error type, discarding the HAL error.
+impl<PinE, SpiE, RstE, BlE> From<Error<PinE, SpiE, RstE, BlE>> for DisplayError {
+    fn from(error: Error<PinE, SpiE, RstE, BlE>) -> Self {
+        match error {
+            Error::Pin(_) | Error::Reset(_) | Error::Backlight(_) | Error::TearingEffect(_) => {
+                DisplayError::DCError
+            }
+            Error::Spi(_) | Error::Timeout => DisplayError::BusWriteError,
+            Error::OutOfBounds => DisplayError::OutOfBoundsError,
+            Error::InvalidFormat => DisplayError::InvalidFormatError,
+        }
+    }
+}
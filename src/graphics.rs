@@ -1,16 +1,27 @@
-use crate::st7789v::{COLS, ROWS};
+use crate::driver::{COLMOD_RGB444, COLMOD_RGB565, COLMOD_RGB666, Lcd};
+use crate::error::DriverError;
+use crate::interface::{Backlight, LcdBus, ResetPin};
+use crate::st7789v::{COLS, Madctl, ROWS};
+use core::marker::PhantomData;
+use display_interface::DisplayError;
+#[cfg(feature = "default_font")]
+use embedded_graphics::mono_font::{MonoTextStyleBuilder, ascii::FONT_10X20};
 use embedded_graphics::{
-    mono_font::{MonoTextStyle, ascii::FONT_10X20},
-    pixelcolor::Rgb565,
+    image::{Image, ImageRaw},
+    mono_font::MonoTextStyle,
+    pixelcolor::{BinaryColor, Gray8, Rgb565, raw::RawU16},
     prelude::*,
-    text::Text,
+    primitives::Rectangle,
+    text::{Alignment, Baseline, Text, TextStyleBuilder},
 };
+use embedded_hal::digital;
 
-use super::st7789v::FRAME_SIZE;
+pub use super::st7789v::{FRAME_SIZE, FRAME_SIZE_444, LINE_SIZE, LINE_SIZE_444, frame_size};
 
-#[derive(Clone, Copy, PartialEq, Eq)]
+#[derive(Clone, Copy, PartialEq, Eq, Default)]
 pub enum DisplayRotation {
     /// No rotation
+    #[default]
     Rotate0,
     /// Rotate by 90 degrees clockwise
     Rotate90,
@@ -20,9 +31,299 @@ pub enum DisplayRotation {
     Rotate270,
 }
 
-impl Default for DisplayRotation {
+impl DisplayRotation {
+    /// MADCTL scan-direction bits that make the controller apply this rotation itself.
+    ///
+    /// | Rotation    | MADCTL      | Native geometry |
+    /// |-------------|-------------|-----------------|
+    /// | `Rotate0`   | —           | 240x320         |
+    /// | `Rotate90`  | `MV \| MX`  | 320x240         |
+    /// | `Rotate180` | `MX \| MY`  | 240x320         |
+    /// | `Rotate270` | `MV \| MY`  | 320x240         |
+    ///
+    /// With these bits set, a buffer drawn at `Rotate0` in [`geometry`](Self::geometry) is
+    /// streamed to the panel as-is; see
+    /// [`Lcd::set_hardware_rotation`](crate::driver::Lcd::set_hardware_rotation).
+    pub fn madctl(self) -> Madctl {
+        match self {
+            DisplayRotation::Rotate0 => Madctl::empty(),
+            DisplayRotation::Rotate90 => Madctl::MV | Madctl::MX,
+            DisplayRotation::Rotate180 => Madctl::MX | Madctl::MY,
+            DisplayRotation::Rotate270 => Madctl::MV | Madctl::MY,
+        }
+    }
+    /// Native geometry of the panel when rotated by [`madctl`](Self::madctl)
+    pub fn geometry(self) -> Geometry {
+        match self {
+            DisplayRotation::Rotate0 | DisplayRotation::Rotate180 => Geometry::PORTRAIT,
+            DisplayRotation::Rotate90 | DisplayRotation::Rotate270 => Geometry::LANDSCAPE,
+        }
+    }
+}
+
+/// Order in which the two bytes of an RGB565 color are sent to the panel.
+///
+/// The ST7789V expects [`ByteOrder::MsbFirst`]. [`ByteOrder::LsbFirst`] is for adapter boards
+/// that swap each byte pair on the way to the controller.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum ByteOrder {
+    /// Most significant byte first
+    #[default]
+    MsbFirst,
+    /// Least significant byte first
+    LsbFirst,
+}
+
+impl ByteOrder {
+    /// Serializes a raw RGB565 color in this byte order
+    pub(crate) fn bytes(self, color: u16) -> [u8; 2] {
+        match self {
+            ByteOrder::MsbFirst => color.to_be_bytes(),
+            ByteOrder::LsbFirst => color.to_le_bytes(),
+        }
+    }
+    /// Reads back a raw RGB565 color stored in this byte order
+    pub(crate) fn color(self, bytes: [u8; 2]) -> u16 {
+        match self {
+            ByteOrder::MsbFirst => u16::from_be_bytes(bytes),
+            ByteOrder::LsbFirst => u16::from_le_bytes(bytes),
+        }
+    }
+    /// Copies pixels stored in byte order `from` into `dst` in this byte order
+    fn convert_from(self, from: ByteOrder, dst: &mut [u8], src: &[u8]) {
+        if self == from {
+            dst.copy_from_slice(src);
+        } else {
+            for (dst, src) in dst.chunks_exact_mut(2).zip(src.chunks_exact(2)) {
+                dst.copy_from_slice(&[src[1], src[0]]);
+            }
+        }
+    }
+}
+
+/// Native panel dimensions as addressed by the controller.
+///
+/// The ST7789V scans the ATK-MD0240 as 240x320 portrait by default. [`Geometry::LANDSCAPE`]
+/// swaps the axes so 320x240 is native, letting landscape-first apps avoid software rotation.
+/// Use the same geometry for the [`Lcd`](crate::driver::Lcd) and its framebuffers.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Geometry {
+    /// Number of columns
+    pub width: u16,
+    /// Number of rows
+    pub height: u16,
+}
+
+impl Geometry {
+    /// 240 columns by 320 rows
+    pub const PORTRAIT: Self = Self {
+        width: COLS,
+        height: ROWS,
+    };
+    /// 320 columns by 240 rows
+    pub const LANDSCAPE: Self = Self {
+        width: ROWS,
+        height: COLS,
+    };
+
+    /// Whether the panel is addressed with rows and columns exchanged (MADCTL MV)
+    pub fn is_landscape(&self) -> bool {
+        self.width > self.height
+    }
+}
+
+impl Default for Geometry {
     fn default() -> Self {
-        DisplayRotation::Rotate0
+        Self::PORTRAIT
+    }
+}
+
+/// Interface pixel format, as selected by the controller's `COLMOD` register.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum PixelFormat {
+    /// 12 bits per pixel, two pixels packed in three bytes
+    Rgb444,
+    /// 16 bits per pixel
+    #[default]
+    Rgb565,
+    /// 18 bits per pixel, sent as three bytes with the colors left-aligned
+    Rgb666,
+}
+
+impl PixelFormat {
+    /// The `COLMOD` register value selecting this format
+    pub fn colmod(self) -> u8 {
+        match self {
+            PixelFormat::Rgb444 => COLMOD_RGB444,
+            PixelFormat::Rgb565 => COLMOD_RGB565,
+            PixelFormat::Rgb666 => COLMOD_RGB666,
+        }
+    }
+    /// The format selected by a `COLMOD` register value, judged by its control interface bits
+    pub fn from_colmod(colmod: u8) -> Option<Self> {
+        match colmod & 0x07 {
+            0b011 => Some(PixelFormat::Rgb444),
+            0b101 => Some(PixelFormat::Rgb565),
+            0b110 => Some(PixelFormat::Rgb666),
+            _ => None,
+        }
+    }
+    /// Number of bits sent over the bus per pixel
+    pub fn bits_per_pixel(self) -> usize {
+        match self {
+            PixelFormat::Rgb444 => 12,
+            PixelFormat::Rgb565 => 16,
+            PixelFormat::Rgb666 => 24,
+        }
+    }
+    /// Number of bytes needed for `pixels` pixels
+    pub fn bytes_for(self, pixels: usize) -> usize {
+        (pixels * self.bits_per_pixel()).div_ceil(8)
+    }
+}
+
+/// A pixel buffer that can be sent to the panel with [`Lcd::flush`](crate::driver::Lcd::flush).
+pub trait Framebuffer {
+    /// Raw pixel bytes, in the order they are streamed to the controller
+    fn bytes(&self) -> &[u8];
+    /// Panel area covered by the buffer as `(start_x, start_y, end_x, end_y)`, inclusive
+    fn area(&self) -> (u16, u16, u16, u16);
+    /// Layout of [`bytes`](Self::bytes); the panel must be set to the same format
+    fn pixel_format(&self) -> PixelFormat {
+        PixelFormat::Rgb565
+    }
+}
+
+/// Fill a raw RGB565 buffer with a single color
+fn fill_color(buffer: &mut [u8], color: Rgb565, byte_order: ByteOrder) {
+    let bytes = byte_order.bytes(color.into_storage());
+    buffer
+        .chunks_exact_mut(2)
+        .for_each(|pixel| pixel.copy_from_slice(&bytes));
+}
+
+/// Fill a packed RGB444 buffer with a single color
+fn fill_color_444(buffer: &mut [u8], color: Rgb565) {
+    let [r, g, b] = to_444(color);
+    let pair = [r << 4 | g, b << 4 | r, g << 4 | b];
+    buffer
+        .chunks_exact_mut(3)
+        .for_each(|pixels| pixels.copy_from_slice(&pair));
+}
+
+/// Truncate an RGB565 color to its upper four bits per channel
+fn to_444(color: Rgb565) -> [u8; 3] {
+    [color.r() >> 1, color.g() >> 2, color.b() >> 1]
+}
+
+/// Number of pixels cut from each end of row `row` (counted from the top or bottom edge) of a
+/// shape with rounded corners of `radius`, keeping pixels whose centers lie inside the arc
+fn corner_inset(radius: u32, row: u32) -> u32 {
+    // In half-pixel units the arc center is at (2r, 2r) and pixel centers at odd coordinates
+    let dy = 2 * radius - 2 * row - 1;
+    let dx = (4 * radius * radius - dy * dy).isqrt();
+    (2 * radius - dx) / 2
+}
+
+/// `sin(d)` for whole degrees `d` in `0..=90`, scaled by 2^14
+const SIN_DEG: [i32; 91] = [
+    0, 286, 572, 857, 1143, 1428, 1713, 1997, 2280, 2563, 2845, 3126, 3406, 3686, 3964, 4240, 4516,
+    4790, 5063, 5334, 5604, 5872, 6138, 6402, 6664, 6924, 7182, 7438, 7692, 7943, 8192, 8438, 8682,
+    8923, 9162, 9397, 9630, 9860, 10087, 10311, 10531, 10749, 10963, 11174, 11381, 11585, 11786,
+    11982, 12176, 12365, 12551, 12733, 12911, 13085, 13255, 13421, 13583, 13741, 13894, 14044,
+    14189, 14330, 14466, 14598, 14726, 14849, 14968, 15082, 15191, 15296, 15396, 15491, 15582,
+    15668, 15749, 15826, 15897, 15964, 16026, 16083, 16135, 16182, 16225, 16262, 16294, 16322,
+    16344, 16362, 16374, 16382, 16384,
+];
+
+/// Unit vector at `degrees` clockwise from the positive x axis (y pointing down), scaled by 2^14
+fn direction(degrees: i32) -> (i32, i32) {
+    let sin = |d: i32| match d.rem_euclid(360) {
+        d @ 0..=90 => SIN_DEG[d as usize],
+        d @ 91..=180 => SIN_DEG[(180 - d) as usize],
+        d @ 181..=270 => -SIN_DEG[(d - 180) as usize],
+        d => -SIN_DEG[(360 - d) as usize],
+    };
+    (sin(degrees + 90), sin(degrees))
+}
+
+/// Largest `dx` with `dx² + dy² <= r² + r`, the half-width of row `dy` of a disc of radius `r`,
+/// or `None` if the row misses the disc
+fn disc_half_width(radius: u32, dy: i32) -> Option<i32> {
+    let limit = (radius * radius + radius) as i64 - (dy as i64 * dy as i64);
+    (limit >= 0).then(|| (limit as u64).isqrt() as i32)
+}
+
+/// Color `step / steps` of the way from `from` to `to`, interpolating each channel linearly
+fn lerp_color(from: Rgb565, to: Rgb565, step: u32, steps: u32) -> Rgb565 {
+    let lerp = |a: u8, b: u8| {
+        if steps == 0 {
+            return a;
+        }
+        (a as i32 + (b as i32 - a as i32) * step as i32 / steps as i32) as u8
+    };
+    Rgb565::new(
+        lerp(from.r(), to.r()),
+        lerp(from.g(), to.g()),
+        lerp(from.b(), to.b()),
+    )
+}
+
+/// Map logical `(x, y)` to unrotated buffer coordinates for a `width` x `height` buffer
+pub(crate) fn rotate(
+    rotation: DisplayRotation,
+    x: usize,
+    y: usize,
+    width: usize,
+    height: usize,
+) -> (usize, usize) {
+    match rotation {
+        DisplayRotation::Rotate0 => (x, y),
+        DisplayRotation::Rotate90 => (width - 1 - y, x),
+        DisplayRotation::Rotate180 => (width - 1 - x, height - 1 - y),
+        DisplayRotation::Rotate270 => (y, height - 1 - x),
+    }
+}
+
+/// Size of the header written by [`bmp_header`]
+#[cfg(any(feature = "heap_alloc", feature = "simulator"))]
+pub(crate) const BMP_HEADER_SIZE: usize = 14 + 40 + 12;
+
+/// File and info headers of a top-down 16-bit RGB565 BMP image of `width` x `height` pixels,
+/// with rows padded to a multiple of four bytes
+#[cfg(any(feature = "heap_alloc", feature = "simulator"))]
+pub(crate) fn bmp_header(width: u32, height: u32) -> [u8; BMP_HEADER_SIZE] {
+    let image_size = (width as usize * 2).next_multiple_of(4) * height as usize;
+    let mut header = [0u8; BMP_HEADER_SIZE];
+    // BITMAPFILEHEADER
+    header[0..2].copy_from_slice(b"BM");
+    header[2..6].copy_from_slice(&((BMP_HEADER_SIZE + image_size) as u32).to_le_bytes());
+    header[10..14].copy_from_slice(&(BMP_HEADER_SIZE as u32).to_le_bytes());
+    // BITMAPINFOHEADER with BI_BITFIELDS, negative height for top-down rows
+    header[14..18].copy_from_slice(&40u32.to_le_bytes());
+    header[18..22].copy_from_slice(&(width as i32).to_le_bytes());
+    header[22..26].copy_from_slice(&(-(height as i32)).to_le_bytes());
+    header[26..28].copy_from_slice(&1u16.to_le_bytes());
+    header[28..30].copy_from_slice(&16u16.to_le_bytes());
+    header[30..34].copy_from_slice(&3u32.to_le_bytes());
+    header[34..38].copy_from_slice(&(image_size as u32).to_le_bytes());
+    // Red, green and blue masks
+    header[54..58].copy_from_slice(&0xF800u32.to_le_bytes());
+    header[58..62].copy_from_slice(&0x07E0u32.to_le_bytes());
+    header[62..66].copy_from_slice(&0x001Fu32.to_le_bytes());
+    header
+}
+
+/// Split `text` into its first line of at most `max_chars` characters and the remainder,
+/// breaking at the last space that fits when there is one
+fn wrap_line(text: &str, max_chars: usize) -> (&str, &str) {
+    let Some((end, _)) = text.char_indices().nth(max_chars) else {
+        return (text, "");
+    };
+    let limit = end + text[end..].chars().next().map_or(0, char::len_utf8);
+    match text[..limit].rfind(' ') {
+        Some(space) if space > 0 => (&text[..space], text[space + 1..].trim_start_matches(' ')),
+        _ => (&text[..end], &text[end..]),
     }
 }
 
@@ -30,57 +331,225 @@ impl Default for DisplayRotation {
 extern crate alloc;
 #[cfg(feature = "heap_alloc")]
 use alloc::vec::Vec;
-pub struct Display2in14 {
-    #[cfg(feature = "stack_alloc")]
-    pub(crate) buffer: [u8; FRAME_SIZE],
+/// Framebuffer holding `N` bytes of RGB565 pixel data.
+///
+/// By default `N` is [`FRAME_SIZE`], covering the whole panel. A smaller `N` gives a strip
+/// buffer spanning the full panel width and the first `N / (width * 2)` rows, which is useful on
+/// MCUs that cannot spare RAM for a complete frame. `N` must be a non-zero multiple of one
+/// portrait row and no larger than a full frame; this is checked at compile time.
+pub struct Display2in14<const N: usize = FRAME_SIZE> {
+    #[cfg(not(feature = "heap_alloc"))]
+    pub(crate) buffer: [u8; N],
     #[cfg(feature = "heap_alloc")]
     pub(crate) buffer: Vec<u8>,
     rotation: DisplayRotation,
+    geometry: Geometry,
+    /// Number of rows held by the buffer for the current geometry
+    lines: u16,
+    /// Byte order colors are stored in
+    byte_order: ByteOrder,
+    /// Style used by `draw_text_default_style`
+    #[cfg(feature = "default_font")]
+    text_style: MonoTextStyle<'static, Rgb565>,
 }
 
 impl Display2in14 {
-    /// Create a buffer with a background color
-    #[cfg(feature = "stack_alloc")]
+    /// Create a full-frame buffer with a background color
+    #[cfg(not(feature = "heap_alloc"))]
     pub fn new(color: Rgb565) -> Self {
-        let color = color.into_storage();
-        let msb = (color >> 8) as u8;
-        let lsb = color as u8;
-        let mut buffer = [0u8; FRAME_SIZE];
-        buffer.chunks_exact_mut(2).for_each(|pixel| {
-            pixel[0] = msb;
-            pixel[1] = lsb;
-        });
+        Self::new_strip(color)
+    }
+    /// Create a full-frame buffer with a background color
+    ///
+    /// # Panics
+    ///
+    /// Panics if `buffer` is not `FRAME_SIZE` bytes long; see [`try_new`](Self::try_new).
+    #[cfg(feature = "heap_alloc")]
+    pub fn new(buffer: Vec<u8>, color: Rgb565) -> Self {
+        Self::new_strip(buffer, color)
+    }
+    /// Create a full-frame buffer with a background color, returning
+    /// [`DisplayError::InvalidFormatError`] instead of panicking if `buffer` is not
+    /// `FRAME_SIZE` bytes long
+    #[cfg(feature = "heap_alloc")]
+    pub fn try_new(buffer: Vec<u8>, color: Rgb565) -> Result<Self, DisplayError> {
+        Self::try_new_strip(buffer, color)
+    }
+}
+
+impl<const N: usize> Display2in14<N> {
+    /// Number of portrait rows covered by the buffer
+    const LINES: u16 = {
+        assert!(N > 0, "Framebuffer size must not be zero");
+        assert!(
+            N.is_multiple_of(LINE_SIZE),
+            "Framebuffer size must be a multiple of one row"
+        );
+        assert!(
+            N <= FRAME_SIZE,
+            "Framebuffer size must not exceed a full frame"
+        );
+        (N / LINE_SIZE) as u16
+    };
+
+    /// Create a strip buffer of `N` bytes with a background color
+    #[cfg(not(feature = "heap_alloc"))]
+    pub fn new_strip(color: Rgb565) -> Self {
+        let mut buffer = [0u8; N];
+        fill_color(&mut buffer, color, ByteOrder::default());
         Self {
             buffer,
             rotation: DisplayRotation::default(),
+            geometry: Geometry::PORTRAIT,
+            lines: Self::LINES,
+            byte_order: ByteOrder::default(),
+            #[cfg(feature = "default_font")]
+            text_style: MonoTextStyle::new(&FONT_10X20, Rgb565::BLACK),
         }
     }
+    /// Create a strip buffer of `N` bytes with a background color
+    ///
+    /// # Panics
+    ///
+    /// Panics if `buffer` is not `N` bytes long; see [`try_new_strip`](Self::try_new_strip).
     #[cfg(feature = "heap_alloc")]
-    pub fn new(mut buffer: Vec<u8>, color: Rgb565) -> Self {
-        if buffer.len() != FRAME_SIZE {
+    pub fn new_strip(buffer: Vec<u8>, color: Rgb565) -> Self {
+        match Self::try_new_strip(buffer, color) {
+            Ok(display) => display,
+            Err(_) => panic!("Incorrect buffer size"),
+        }
+    }
+    /// Create a strip buffer of `N` bytes with a background color, returning
+    /// [`DisplayError::InvalidFormatError`] instead of panicking if `buffer` is not `N` bytes
+    /// long
+    ///
+    /// The buffer is usually allocated at run time, so its length cannot be checked at
+    /// compile time like `N` itself; this lets firmware report a misconfigured size.
+    #[cfg(feature = "heap_alloc")]
+    pub fn try_new_strip(mut buffer: Vec<u8>, color: Rgb565) -> Result<Self, DisplayError> {
+        if buffer.len() != N {
+            return Err(DisplayError::InvalidFormatError);
+        }
+        fill_color(&mut buffer, color, ByteOrder::default());
+        Ok(Self::from_raw(buffer))
+    }
+    /// Create a buffer of `N` bytes showing a prebuilt image, such as a splash screen stored
+    /// in flash.
+    ///
+    /// `data` holds big-endian RGB565 pixels in portrait row order, exactly as
+    /// [`raw_bytes`](Self::raw_bytes) returns them; it is copied into the buffer.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `data` is not exactly `N` bytes long.
+    #[cfg(not(feature = "heap_alloc"))]
+    pub fn from_raw(data: &[u8]) -> Self {
+        if data.len() != N {
+            panic!("Incorrect buffer size")
+        }
+        let mut display = Self::new_strip(Rgb565::BLACK);
+        display.buffer.copy_from_slice(data);
+        display
+    }
+    /// Create a buffer of `N` bytes showing a prebuilt image, such as a splash screen loaded
+    /// from flash.
+    ///
+    /// `buffer` holds big-endian RGB565 pixels in portrait row order, exactly as
+    /// [`raw_bytes`](Self::raw_bytes) returns them, and becomes the framebuffer without a copy.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `buffer` is not exactly `N` bytes long.
+    #[cfg(feature = "heap_alloc")]
+    pub fn from_raw(buffer: Vec<u8>) -> Self {
+        if buffer.len() != N {
             panic!("Incorrect buffer size")
         }
-        let color = color.into_storage();
-        let msb = (color >> 8) as u8;
-        let lsb = color as u8;
-        buffer.chunks_exact_mut(2).for_each(|pixel| {
-            pixel[0] = msb;
-            pixel[1] = lsb;
-        });
         Self {
             buffer,
             rotation: DisplayRotation::default(),
+            geometry: Geometry::PORTRAIT,
+            lines: Self::LINES,
+            byte_order: ByteOrder::default(),
+            #[cfg(feature = "default_font")]
+            text_style: MonoTextStyle::new(&FONT_10X20, Rgb565::BLACK),
         }
     }
     /// Clear the buffer with a background color
     pub fn clear_buffer(&mut self, color: Rgb565) {
-        let color = color.into_storage();
-        let msb = (color >> 8) as u8;
-        let lsb = color as u8;
-        self.buffer.chunks_exact_mut(2).for_each(|pixel| {
-            pixel[0] = msb;
-            pixel[1] = lsb;
-        });
+        fill_color(&mut self.buffer, color, self.byte_order);
+    }
+    /// Clear only `rect` with a background color, e.g. to erase a field before redrawing it.
+    ///
+    /// `rect` is in logical (rotated) coordinates and is clipped to the buffer.
+    pub fn clear_rect(&mut self, rect: Rectangle, color: Rgb565) {
+        self.fill_rounded_rect(rect, 0, color);
+    }
+
+    /// Writes `color` at the logical pixel `(x, y)` and returns the color it replaced, or
+    /// `None` without writing if the pixel lies outside the buffer.
+    ///
+    /// Keeping the returned colors is enough to undo a stroke or move a cursor drawn over the
+    /// content.
+    pub fn replace_pixel(&mut self, x: u16, y: u16, color: Rgb565) -> Option<Rgb565> {
+        let old = self.pixel(x, y)?;
+        let idx = self.get_location(x, y);
+        self.buffer[idx..idx + 2].copy_from_slice(&self.byte_order.bytes(color.into_storage()));
+        Some(old)
+    }
+
+    /// Returns the color of the logical pixel `(x, y)`, or `None` if it lies outside the
+    /// buffer.
+    ///
+    /// The bytes are decoded with the buffer's [`ByteOrder`] at the rotation-aware index, so
+    /// this reads back exactly what drawing at `(x, y)` wrote.
+    pub fn pixel(&self, x: u16, y: u16) -> Option<Rgb565> {
+        if !self.contains(Point::new(x as i32, y as i32)) {
+            return None;
+        }
+        let idx = self.get_location(x, y);
+        let raw = self
+            .byte_order
+            .color([self.buffer[idx], self.buffer[idx + 1]]);
+        Some(Rgb565::from(RawU16::new(raw)))
+    }
+
+    /// Blends every pixel inside `rect` toward `color` by `alpha / 255`, for a scrim behind a
+    /// dialog or a highlight, without redrawing what is underneath.
+    ///
+    /// Each channel is interpolated in its own RGB565 range (5, 6 and 5 bits), so an `alpha`
+    /// of 0 leaves the pixels untouched and 255 replaces them with `color`. `rect` is in
+    /// logical coordinates and clipped to the buffer.
+    pub fn blend_region(&mut self, rect: Rectangle, color: Rgb565, alpha: u8) {
+        for point in rect.intersection(&self.bounding_box()).points() {
+            let idx = self.get_location(point.x as u16, point.y as u16);
+            let old = self
+                .byte_order
+                .color([self.buffer[idx], self.buffer[idx + 1]]);
+            let blended = lerp_color(Rgb565::from(RawU16::new(old)), color, alpha as u32, 255);
+            self.buffer[idx..idx + 2]
+                .copy_from_slice(&self.byte_order.bytes(blended.into_storage()));
+        }
+    }
+
+    pub fn get_geometry(&self) -> Geometry {
+        self.geometry
+    }
+
+    /// Set the native panel geometry the buffer is laid out for.
+    ///
+    /// The buffer keeps its `N` bytes and now holds `N / (geometry.width * 2)` rows of the new
+    /// width. Its content is not rearranged.
+    ///
+    /// # Panics
+    /// Panics if `N` is not a multiple of one row of the new geometry or exceeds a full frame.
+    pub fn set_geometry(&mut self, geometry: Geometry) {
+        let line_size = PixelFormat::Rgb565.bytes_for(geometry.width as usize);
+        if !N.is_multiple_of(line_size) || N / line_size > geometry.height as usize {
+            panic!("Incorrect buffer size")
+        }
+        self.geometry = geometry;
+        self.lines = (N / line_size) as u16;
     }
 
     pub fn get_rotation(&self) -> DisplayRotation {
@@ -91,62 +560,1211 @@ impl Display2in14 {
         self.rotation = rotation
     }
 
-    pub fn draw_text(&mut self, x: u16, y: u16, text: &str, style: MonoTextStyle<Rgb565>) {
-        Text::new(
+    pub fn get_byte_order(&self) -> ByteOrder {
+        self.byte_order
+    }
+
+    /// Set the byte order used for colors drawn from now on.
+    ///
+    /// Pixels already in the buffer are not converted; call `clear_buffer` afterwards to start
+    /// from a consistent state.
+    pub fn set_byte_order(&mut self, byte_order: ByteOrder) {
+        self.byte_order = byte_order
+    }
+
+    /// Whether `point` lies within the buffer's logical (rotated) bounds
+    pub fn contains(&self, point: Point) -> bool {
+        self.bounding_box().contains(point)
+    }
+
+    /// Draw text with its baseline starting at `(x, y)`.
+    ///
+    /// Returns the bounding box of the drawn text, so the next element can be placed right
+    /// after it (for example at `bounds.top_left.x + bounds.size.width`).
+    pub fn draw_text(
+        &mut self,
+        x: u16,
+        y: u16,
+        text: &str,
+        style: MonoTextStyle<Rgb565>,
+    ) -> Result<Rectangle, DisplayError> {
+        let text = Text::new(
             text,
             Point {
                 x: x as i32,
                 y: y as i32,
             },
             style,
+        );
+        text.draw(self)?;
+        Ok(text.bounding_box())
+    }
+
+    /// Draw text with the default style set by `set_default_text_style`, returning its
+    /// bounding box
+    ///
+    /// Requires the `default_font` feature, which links `FONT_10X20` into the binary.
+    #[cfg(feature = "default_font")]
+    pub fn draw_text_default_style(
+        &mut self,
+        x: u16,
+        y: u16,
+        text: &str,
+    ) -> Result<Rectangle, DisplayError> {
+        self.draw_text(x, y, text, self.text_style)
+    }
+
+    /// Draw text in the default font with `fg` glyphs on a `bg` background, returning its
+    /// bounding box
+    ///
+    /// Every pixel of each character cell is written, so redrawing changing text such as a
+    /// counter replaces the previous glyphs without clearing them first (pad shorter strings
+    /// with spaces to cover longer ones).
+    #[cfg(feature = "default_font")]
+    pub fn draw_text_with_bg(
+        &mut self,
+        x: u16,
+        y: u16,
+        text: &str,
+        fg: Rgb565,
+        bg: Rgb565,
+    ) -> Result<Rectangle, DisplayError> {
+        let style = MonoTextStyleBuilder::from(&self.text_style)
+            .text_color(fg)
+            .background_color(bg)
+            .build();
+        self.draw_text(x, y, text, style)
+    }
+
+    /// Draw text word-wrapped to fit inside `bounds`.
+    ///
+    /// Lines break at spaces where possible and mid-word otherwise; `\n` starts a new line.
+    /// Line advance and the number of characters per line are derived from the font in
+    /// `style`. Lines that would not fit entirely above the bottom of `bounds` are dropped.
+    pub fn draw_text_wrapped(
+        &mut self,
+        bounds: Rectangle,
+        text: &str,
+        style: MonoTextStyle<Rgb565>,
+    ) -> Result<(), DisplayError> {
+        let font = style.font;
+        let advance = font.character_size.width + font.character_spacing;
+        let max_chars = ((bounds.size.width + font.character_spacing) / advance) as usize;
+        let line_height = font.character_size.height as i32;
+        if max_chars == 0 {
+            return Ok(());
+        }
+        let bottom = bounds.top_left.y + bounds.size.height as i32;
+        let mut position = bounds.top_left;
+        for paragraph in text.split('\n') {
+            let mut rest = paragraph;
+            loop {
+                if position.y + line_height > bottom {
+                    return Ok(());
+                }
+                let (line, next) = wrap_line(rest, max_chars);
+                Text::with_baseline(line, position, style, Baseline::Top).draw(self)?;
+                position.y += line_height;
+                rest = next;
+                if rest.is_empty() {
+                    break;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Style used by `draw_text_default_style` (initially `FONT_10X20` in black)
+    #[cfg(feature = "default_font")]
+    pub fn default_text_style(&self) -> MonoTextStyle<'static, Rgb565> {
+        self.text_style
+    }
+
+    /// Set the style used by `draw_text_default_style`
+    #[cfg(feature = "default_font")]
+    pub fn set_default_text_style(&mut self, style: MonoTextStyle<'static, Rgb565>) {
+        self.text_style = style
+    }
+
+    /// Draw text positioned relative to `(x, y)` with the given alignment and baseline.
+    ///
+    /// For example, `Alignment::Center` with `Baseline::Middle` centers the text on `(x, y)`,
+    /// and `Alignment::Left` with `Baseline::Top` places its top-left corner there.
+    pub fn draw_text_aligned(
+        &mut self,
+        x: u16,
+        y: u16,
+        text: &str,
+        style: MonoTextStyle<Rgb565>,
+        alignment: Alignment,
+        baseline: Baseline,
+    ) -> Result<(), DisplayError> {
+        let text_style = TextStyleBuilder::new()
+            .alignment(alignment)
+            .baseline(baseline)
+            .build();
+        Text::with_text_style(
+            text,
+            Point {
+                x: x as i32,
+                y: y as i32,
+            },
+            style,
+            text_style,
         )
-        .draw(self)
-        .unwrap();
+        .draw(self)?;
+        Ok(())
+    }
+
+    /// Draws big-endian RGB565 image data with its top-left corner at `top_left`.
+    ///
+    /// The bytes are swapped on the way in when the buffer uses [`ByteOrder::LsbFirst`].
+    ///
+    /// `data` and `width` are what you would pass to `ImageRaw::<Rgb565>::new`; `ImageRaw` does
+    /// not expose its bytes, so they are taken directly. With no rotation, each visible row is
+    /// copied into the buffer with a single `copy_from_slice`, which is much faster than drawing
+    /// an `Image` pixel by pixel. Rotated buffers fall back to the generic `Image` path. Parts
+    /// of the image outside the buffer are clipped.
+    ///
+    /// Returns [`DisplayError::InvalidFormatError`] without drawing if `width` is zero or
+    /// `data` does not hold a whole number of rows.
+    pub fn draw_image_raw(
+        &mut self,
+        top_left: Point,
+        data: &[u8],
+        width: u32,
+    ) -> Result<(), DisplayError> {
+        let stride = width as usize * 2;
+        if stride == 0 || !data.len().is_multiple_of(stride) {
+            return Err(DisplayError::InvalidFormatError);
+        }
+        if self.rotation != DisplayRotation::Rotate0 {
+            let raw = ImageRaw::<Rgb565>::new(data, width);
+            return Image::new(&raw, top_left).draw(self);
+        }
+        let height = (data.len() / stride) as u32;
+        let visible =
+            Rectangle::new(top_left, Size::new(width, height)).intersection(&self.bounding_box());
+        if visible.is_zero_sized() {
+            return Ok(());
+        }
+        let len = visible.size.width as usize * 2;
+        for y in visible.rows() {
+            let src_x = (visible.top_left.x - top_left.x) as usize;
+            let src_y = (y - top_left.y) as usize;
+            let from = (src_y * width as usize + src_x) * 2;
+            let to = self.get_location(visible.top_left.x as u16, y as u16);
+            self.byte_order.convert_from(
+                ByteOrder::MsbFirst,
+                &mut self.buffer[to..to + len],
+                &data[from..from + len],
+            );
+        }
+        Ok(())
+    }
+
+    /// Draws a 1-bit-per-pixel bitmap with its top-left corner at `top_left`.
+    ///
+    /// `bits` holds rows of `width` pixels, most significant bit first, each row padded to a
+    /// whole byte (the layout of `ImageRaw::<BinaryColor>`). Set bits are drawn in `fg`, clear
+    /// bits in `bg`, or left untouched when `bg` is `None`. A 16x16 icon takes 32 bytes instead
+    /// of 512 as RGB565. The bitmap is drawn in logical (rotated) coordinates and clipped to
+    /// the buffer.
+    pub fn draw_mono_bitmap(
+        &mut self,
+        top_left: Point,
+        width: u32,
+        bits: &[u8],
+        fg: Rgb565,
+        bg: Option<Rgb565>,
+    ) {
+        let stride = width.div_ceil(8) as usize;
+        if stride == 0 {
+            return;
+        }
+        let size = self.size();
+        for (row, line) in bits.chunks_exact(stride).enumerate() {
+            let y = top_left.y + row as i32;
+            if y < 0 || y >= size.height as i32 {
+                continue;
+            }
+            for column in 0..width {
+                let x = top_left.x + column as i32;
+                if x < 0 || x >= size.width as i32 {
+                    continue;
+                }
+                let set = line[column as usize / 8] & (0x80 >> (column % 8)) != 0;
+                if let Some(color) = if set { Some(fg) } else { bg } {
+                    self.set_pixel(x as u16, y as u16, color);
+                }
+            }
+        }
+    }
+
+    /// Inverts the colors of every pixel in `rect` in place.
+    ///
+    /// Handy for highlighting a menu entry without redrawing it; inverting the same area again
+    /// restores it. `rect` is in logical (rotated) coordinates and is clipped to the buffer.
+    pub fn invert_region(&mut self, rect: Rectangle) {
+        let visible = rect.intersection(&self.bounding_box());
+        if visible.is_zero_sized() {
+            return;
+        }
+        if self.rotation == DisplayRotation::Rotate0 {
+            let len = visible.size.width as usize * 2;
+            for y in visible.rows() {
+                let start = self.get_location(visible.top_left.x as u16, y as u16);
+                self.buffer[start..start + len]
+                    .iter_mut()
+                    .for_each(|byte| *byte = !*byte);
+            }
+            return;
+        }
+        for point in visible.points() {
+            let idx = self.get_location(point.x as u16, point.y as u16);
+            self.buffer[idx] = !self.buffer[idx];
+            self.buffer[idx + 1] = !self.buffer[idx + 1];
+        }
+    }
+
+    /// Fills `rect` with `color`, rounding its corners with `radius`.
+    ///
+    /// Each row is written as one span straight into the buffer, with the corner arcs computed
+    /// per row, which is much faster than drawing an embedded-graphics `RoundedRectangle`
+    /// through [`DrawTarget`]. `rect` is in logical (rotated) coordinates and is clipped to the
+    /// buffer; `radius` is limited to half the shorter side.
+    pub fn fill_rounded_rect(&mut self, rect: Rectangle, radius: u32, color: Rgb565) {
+        let Size { width, height } = rect.size;
+        let radius = radius.min(width / 2).min(height / 2);
+        let bytes = self.byte_order.bytes(color.into_storage());
+        let bounds = self.bounding_box();
+        for (row, y) in rect.rows().enumerate() {
+            let from_edge = (row as u32).min(height - 1 - row as u32);
+            let inset = if from_edge < radius {
+                corner_inset(radius, from_edge)
+            } else {
+                0
+            };
+            let span = Rectangle::new(
+                Point::new(rect.top_left.x + inset as i32, y),
+                Size::new(width - 2 * inset, 1),
+            )
+            .intersection(&bounds);
+            if span.is_zero_sized() {
+                continue;
+            }
+            if self.rotation == DisplayRotation::Rotate0 {
+                let start = self.get_location(span.top_left.x as u16, y as u16);
+                self.buffer[start..start + span.size.width as usize * 2]
+                    .chunks_exact_mut(2)
+                    .for_each(|pixel| pixel.copy_from_slice(&bytes));
+            } else {
+                for point in span.points() {
+                    let idx = self.get_location(point.x as u16, point.y as u16);
+                    self.buffer[idx..idx + 2].copy_from_slice(&bytes);
+                }
+            }
+        }
     }
 
-    pub fn draw_text_default_style(&mut self, x: u16, y: u16, text: &str) {
-        let style = MonoTextStyle::new(&FONT_10X20, Rgb565::BLACK);
-        self.draw_text(x, y, text, style);
+    /// Draws a horizontal progress bar in `rect`: a one-pixel `border`, filled with `fill` from
+    /// the left up to `fraction` of its inner width and with `track` for the rest.
+    ///
+    /// `fraction` is clamped to `0.0..=1.0` (NaN counts as empty). Every part is written as
+    /// spans straight into the buffer like [`fill_rounded_rect`](Self::fill_rounded_rect), so
+    /// redrawing the bar on each update is cheap. `rect` is in logical (rotated) coordinates
+    /// and is clipped to the buffer.
+    pub fn draw_progress_bar(
+        &mut self,
+        rect: Rectangle,
+        fraction: f32,
+        fill: Rgb565,
+        track: Rgb565,
+        border: Rgb565,
+    ) {
+        let inner = rect.offset(-1);
+        if inner.is_zero_sized() {
+            self.clear_rect(rect, border);
+            return;
+        }
+        let Size { width, height } = rect.size;
+        let edges = [
+            Rectangle::new(rect.top_left, Size::new(width, 1)),
+            Rectangle::new(
+                rect.top_left + Point::new(0, height as i32 - 1),
+                Size::new(width, 1),
+            ),
+            Rectangle::new(inner.top_left - Point::new(1, 0), Size::new(1, height - 2)),
+            Rectangle::new(
+                inner.top_left + Point::new(width as i32 - 2, 0),
+                Size::new(1, height - 2),
+            ),
+        ];
+        for edge in edges {
+            self.clear_rect(edge, border);
+        }
+        let fraction = if fraction.is_nan() {
+            0.0
+        } else {
+            fraction.clamp(0.0, 1.0)
+        };
+        let filled = (inner.size.width as f32 * fraction + 0.5) as u32;
+        let (done, rest) = (
+            Rectangle::new(inner.top_left, Size::new(filled, inner.size.height)),
+            Rectangle::new(
+                inner.top_left + Point::new(filled as i32, 0),
+                Size::new(inner.size.width - filled, inner.size.height),
+            ),
+        );
+        self.clear_rect(done, fill);
+        self.clear_rect(rest, track);
     }
 
+    /// Fills a circle of `radius` around `center` with `color`.
+    ///
+    /// Pixels with `dx² + dy² <= radius² + radius` are filled, which rounds the outline the
+    /// same way as [`draw_arc`](Self::draw_arc). Each row is written as one span straight
+    /// into the buffer, much faster than an embedded-graphics `Circle` drawn through
+    /// [`DrawTarget`]. Coordinates are logical (rotated) and the circle is clipped to the
+    /// buffer.
+    pub fn draw_filled_circle(&mut self, center: Point, radius: u32, color: Rgb565) {
+        let radius_i = radius as i32;
+        for dy in -radius_i..=radius_i {
+            if let Some(half) = disc_half_width(radius, dy) {
+                let span = Rectangle::new(
+                    center + Point::new(-half, dy),
+                    Size::new(2 * half as u32 + 1, 1),
+                );
+                self.clear_rect(span, color);
+            }
+        }
+    }
+
+    /// Draws a ring segment of `radius` around `center`, `thickness` pixels wide measured
+    /// inwards, from `start_deg` clockwise to `end_deg`, e.g. the scale of a gauge.
+    ///
+    /// Angles are whole degrees clockwise from 3 o'clock, like the embedded-graphics `Arc`;
+    /// `end_deg` may exceed 360 to sweep past 3 o'clock (a gauge from 135 to 405 leaves a gap
+    /// at the bottom). A sweep of 360 degrees or more draws the whole ring, and one where
+    /// `end_deg <= start_deg` draws nothing. Only the pixels of the ring's bounding rows are
+    /// visited and the angle test uses integer cross products, so no floating point or
+    /// trigonometry runs per pixel. Coordinates are logical (rotated) and the arc is clipped
+    /// to the buffer.
+    pub fn draw_arc(
+        &mut self,
+        center: Point,
+        radius: u32,
+        start_deg: i32,
+        end_deg: i32,
+        thickness: u32,
+        color: Rgb565,
+    ) {
+        let sweep = end_deg.saturating_sub(start_deg);
+        if sweep <= 0 || thickness == 0 {
+            return;
+        }
+        let (sx, sy) = direction(start_deg);
+        let (ex, ey) = direction(end_deg);
+        // Cross products are positive for points clockwise of a direction (y points down)
+        let in_sweep = |dx: i32, dy: i32| {
+            let after_start = sx * dy - sy * dx >= 0;
+            let before_end = dx * ey - dy * ex >= 0;
+            match sweep {
+                360.. => true,
+                181..=359 => after_start || before_end,
+                _ => after_start && before_end,
+            }
+        };
+        let inner = radius.checked_sub(thickness);
+        let bytes = self.byte_order.bytes(color.into_storage());
+        let radius_i = radius as i32;
+        for dy in -radius_i..=radius_i {
+            let Some(outer) = disc_half_width(radius, dy) else {
+                continue;
+            };
+            let hole = inner.and_then(|inner| disc_half_width(inner, dy));
+            for dx in -outer..=outer {
+                if hole.is_some_and(|hole| dx.abs() <= hole) || !in_sweep(dx, dy) {
+                    continue;
+                }
+                let point = center + Point::new(dx, dy);
+                if self.contains(point) {
+                    let idx = self.get_location(point.x as u16, point.y as u16);
+                    self.buffer[idx..idx + 2].copy_from_slice(&bytes);
+                }
+            }
+        }
+    }
+
+    /// Fills the buffer with a vertical gradient from `top` to `bottom`.
+    ///
+    /// Each channel is interpolated linearly per logical row, so the gradient runs from the top
+    /// to the bottom of the screen whatever the rotation. Rows are written straight into the
+    /// buffer, which is much faster than drawing one line primitive per row.
+    pub fn fill_gradient(&mut self, top: Rgb565, bottom: Rgb565) {
+        let height = self.size().height;
+        let width = self.geometry.width as usize;
+        let lines = self.lines as usize;
+        let stride = width * 2;
+        for y in 0..height {
+            let color = lerp_color(top, bottom, y, height - 1);
+            let bytes = self.byte_order.bytes(color.into_storage());
+            let (x, row) = rotate(self.rotation, 0, y as usize, width, lines);
+            match self.rotation {
+                DisplayRotation::Rotate0 | DisplayRotation::Rotate180 => self.buffer
+                    [row * stride..(row + 1) * stride]
+                    .chunks_exact_mut(2)
+                    .for_each(|pixel| pixel.copy_from_slice(&bytes)),
+                DisplayRotation::Rotate90 | DisplayRotation::Rotate270 => {
+                    for row in 0..lines {
+                        let idx = row * stride + x * 2;
+                        self.buffer[idx..idx + 2].copy_from_slice(&bytes);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Copies a rectangular region of `src` into this buffer with its top-left corner at `dst`.
+    ///
+    /// Both rectangles are given in each buffer's own logical (rotated) coordinates. The source
+    /// region is clipped to `src` and the destination to this buffer, so parts falling outside
+    /// either buffer are skipped.
+    pub fn blit<const M: usize>(&mut self, src: &Display2in14<M>, src_rect: Rectangle, dst: Point) {
+        let src_rect = src_rect.intersection(&src.bounding_box());
+        let offset = dst - src_rect.top_left;
+        let dst_rect = src_rect
+            .translate(offset)
+            .intersection(&self.bounding_box());
+        for point in dst_rect.points() {
+            let from = point - offset;
+            let from = src.get_location(from.x as u16, from.y as u16);
+            let to = self.get_location(point.x as u16, point.y as u16);
+            self.byte_order.convert_from(
+                src.byte_order,
+                &mut self.buffer[to..to + 2],
+                &src.buffer[from..from + 2],
+            );
+        }
+    }
+
+    /// Iterates over the raw bytes of buffer rows `y0..y1`, one slice per row.
+    ///
+    /// Each slice is one row stride long: `width * 2` bytes of RGB565, big-endian unless the
+    /// byte order was changed with `set_byte_order`. Rows are taken in buffer order, which
+    /// matches logical coordinates only with `DisplayRotation::Rotate0`. The range is clamped
+    /// to the rows held by the buffer.
+    pub fn region_rows(&self, y0: u16, y1: u16) -> impl Iterator<Item = &[u8]> {
+        let stride = self.geometry.width as usize * 2;
+        let y1 = y1.min(self.lines) as usize;
+        let y0 = (y0 as usize).min(y1);
+        self.buffer[y0 * stride..y1 * stride].chunks_exact(stride)
+    }
+
+    /// The whole pixel buffer, exactly as it is streamed to the panel
+    pub fn raw_bytes(&self) -> &[u8] {
+        &self.buffer
+    }
+
+    /// Encodes the buffer as a 16-bit RGB565 BMP image, as seen in logical (rotated) coordinates.
+    ///
+    /// The image uses a `BITMAPINFOHEADER` with `BI_BITFIELDS` masks and a top-down row order,
+    /// so it opens in common image viewers and is easy to decode in host-side tests.
+    #[cfg(feature = "heap_alloc")]
+    pub fn to_bmp(&self) -> Vec<u8> {
+        let size = self.size();
+        let stride = (size.width as usize * 2).next_multiple_of(4);
+        let mut bmp = Vec::with_capacity(BMP_HEADER_SIZE + stride * size.height as usize);
+        bmp.extend_from_slice(&bmp_header(size.width, size.height));
+        for y in 0..size.height {
+            let row_start = bmp.len();
+            for x in 0..size.width {
+                let idx = self.get_location(x as u16, y as u16);
+                let color = self
+                    .byte_order
+                    .color([self.buffer[idx], self.buffer[idx + 1]]);
+                bmp.extend_from_slice(&color.to_le_bytes());
+            }
+            bmp.resize(row_start + stride, 0);
+        }
+        bmp
+    }
+
+    /// Shifts the content up by `lines` logical rows and fills the rows freed at the bottom with
+    /// `color`.
+    ///
+    /// With no rotation the rows are moved with a single `copy_within`; rotated buffers are
+    /// shifted pixel by pixel.
+    pub fn scroll_up(&mut self, lines: u32, color: Rgb565) {
+        let size = self.size();
+        let lines = lines.min(size.height);
+        let kept = size.height - lines;
+        if self.rotation == DisplayRotation::Rotate0 {
+            let row = self.geometry.width as usize * 2;
+            let shift = lines as usize * row;
+            let end = size.height as usize * row;
+            self.buffer.copy_within(shift..end, 0);
+        } else {
+            for y in 0..kept {
+                for x in 0..size.width {
+                    let from = self.get_location(x as u16, (y + lines) as u16);
+                    let to = self.get_location(x as u16, y as u16);
+                    self.buffer.copy_within(from..from + 2, to);
+                }
+            }
+        }
+        let freed = Rectangle::new(Point::new(0, kept as i32), Size::new(size.width, lines));
+        let _ = self.fill_solid(&freed, color);
+    }
+
+    /// Byte offset of the logical pixel `(x, y)` in the buffer, accounting for rotation
     fn get_location(&self, x: u16, y: u16) -> usize {
         let x = x as usize;
         let y = y as usize;
-        match self.rotation {
-            DisplayRotation::Rotate0 => (y * COLS as usize + x) * 2,
-            _ => {
-                todo!();
+        let width = self.geometry.width as usize;
+        let (x, y) = rotate(self.rotation, x, y, width, self.lines as usize);
+        (y * width + x) * 2
+    }
+    fn set_pixel(&mut self, x: u16, y: u16, color: Rgb565) {
+        let size = self.size();
+        if x as u32 >= size.width || y as u32 >= size.height {
+            return;
+        }
+        let idx = self.get_location(x, y);
+        let bytes = self.byte_order.bytes(color.into_storage());
+        self.buffer[idx..idx + 2].copy_from_slice(&bytes);
+    }
+}
+
+impl<const N: usize> Framebuffer for Display2in14<N> {
+    fn bytes(&self) -> &[u8] {
+        &self.buffer
+    }
+    fn area(&self) -> (u16, u16, u16, u16) {
+        (0, 0, self.geometry.width - 1, self.lines - 1)
+    }
+}
+
+impl<const N: usize> OriginDimensions for Display2in14<N> {
+    fn size(&self) -> Size {
+        match self.get_rotation() {
+            DisplayRotation::Rotate0 | DisplayRotation::Rotate180 => {
+                Size::new(self.geometry.width as u32, self.lines as u32)
+            }
+            DisplayRotation::Rotate90 | DisplayRotation::Rotate270 => {
+                Size::new(self.lines as u32, self.geometry.width as u32)
+            }
+        }
+    }
+}
+
+impl<const N: usize> DrawTarget for Display2in14<N> {
+    type Color = Rgb565;
+
+    type Error = DisplayError;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        pixels.into_iter().try_for_each(|pixel| {
+            let ((x, y), color) = ((pixel.0.x as u16, pixel.0.y as u16), pixel.1);
+            self.set_pixel(x, y, color);
+            Ok(())
+        })
+    }
+
+    fn fill_contiguous<I>(&mut self, area: &Rectangle, colors: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Self::Color>,
+    {
+        if self.rotation != DisplayRotation::Rotate0 {
+            return self.draw_iter(
+                area.points()
+                    .zip(colors)
+                    .map(|(point, color)| Pixel(point, color)),
+            );
+        }
+        let visible = area.intersection(&self.bounding_box());
+        if visible.is_zero_sized() {
+            return Ok(());
+        }
+        let width = area.size.width as usize;
+        let skip_left = (visible.top_left.x - area.top_left.x) as usize;
+        let skip_right = width - skip_left - visible.size.width as usize;
+        let mut colors = colors.into_iter();
+        for y in area.rows() {
+            if !visible.rows().contains(&y) {
+                colors.by_ref().take(width).for_each(drop);
+                continue;
             }
+            colors.by_ref().take(skip_left).for_each(drop);
+            let start =
+                (y as usize * self.geometry.width as usize + visible.top_left.x as usize) * 2;
+            let end = start + visible.size.width as usize * 2;
+            self.buffer[start..end]
+                .chunks_exact_mut(2)
+                .zip(colors.by_ref())
+                .for_each(|(pixel, color)| {
+                    pixel.copy_from_slice(&self.byte_order.bytes(color.into_storage()))
+                });
+            colors.by_ref().take(skip_right).for_each(drop);
+        }
+        Ok(())
+    }
+}
+
+/// A pair of [`Display2in14`] buffers for flicker-free animation.
+///
+/// Render into the back buffer with [`back_mut`](Self::back_mut), then [`swap`](Self::swap)
+/// it to the front and [`flush`](Self::flush) it while the next frame is drawn into the other
+/// buffer. This holds two complete buffers, so it doubles framebuffer RAM (300 KB for two full
+/// frames); with `stack_alloc`, consider strip buffers or `heap_alloc` on tight MCUs.
+pub struct DoubleBuffered<const N: usize = FRAME_SIZE> {
+    buffers: [Display2in14<N>; 2],
+    /// Index of the front buffer in `buffers`
+    front: usize,
+}
+
+impl<const N: usize> DoubleBuffered<N> {
+    /// Wrap two buffers; `front` is shown first and `back` is drawn into
+    pub fn new(front: Display2in14<N>, back: Display2in14<N>) -> Self {
+        Self {
+            buffers: [front, back],
+            front: 0,
         }
     }
+    /// The buffer currently shown on the panel
+    pub fn front(&self) -> &Display2in14<N> {
+        &self.buffers[self.front]
+    }
+    /// The buffer being rendered
+    pub fn back(&self) -> &Display2in14<N> {
+        &self.buffers[self.front ^ 1]
+    }
+    /// Mutable access to the buffer being rendered
+    pub fn back_mut(&mut self) -> &mut Display2in14<N> {
+        &mut self.buffers[self.front ^ 1]
+    }
+    /// Exchange the front and back buffers
+    pub fn swap(&mut self) {
+        self.front ^= 1;
+    }
+    /// Sends the front buffer to the display
+    pub fn flush<SPI, RST, WR, PWR>(
+        &self,
+        lcd: &mut Lcd<SPI, RST, WR, PWR>,
+    ) -> Result<(), DriverError<SPI, RST, WR, PWR>>
+    where
+        SPI: LcdBus<WR>,
+        RST: ResetPin,
+        WR: digital::ErrorType,
+        PWR: Backlight,
+    {
+        lcd.flush(self.front())
+    }
+}
+
+/// Framebuffer holding `N` bytes of packed 12-bit (RGB444) pixel data.
+///
+/// Two horizontally adjacent pixels share three bytes (`RG`, `BR`, `GB`), so a full frame
+/// needs [`FRAME_SIZE_444`] bytes (about 115 KB) instead of 150 KB. Colors are drawn as
+/// `Rgb565` and truncated to four bits per channel. The panel must be switched to
+/// [`PixelFormat::Rgb444`] before the buffer is flushed with
+/// [`Lcd::flush`](crate::driver::Lcd::flush).
+///
+/// As with [`Display2in14`], a smaller `N` gives a strip buffer. `N` must be a non-zero
+/// multiple of one packed portrait row and no larger than a full frame.
+pub struct Display2in14Rgb444<const N: usize = FRAME_SIZE_444> {
+    #[cfg(not(feature = "heap_alloc"))]
+    pub(crate) buffer: [u8; N],
+    #[cfg(feature = "heap_alloc")]
+    pub(crate) buffer: Vec<u8>,
+    rotation: DisplayRotation,
+    geometry: Geometry,
+    /// Number of rows held by the buffer for the current geometry
+    lines: u16,
+}
+
+impl<const N: usize> Display2in14Rgb444<N> {
+    /// Number of portrait rows covered by the buffer
+    const LINES: u16 = {
+        assert!(N > 0, "Framebuffer size must not be zero");
+        assert!(
+            N.is_multiple_of(LINE_SIZE_444),
+            "Framebuffer size must be a multiple of one row"
+        );
+        assert!(
+            N <= FRAME_SIZE_444,
+            "Framebuffer size must not exceed a full frame"
+        );
+        (N / LINE_SIZE_444) as u16
+    };
+
+    /// Create a buffer of `N` bytes with a background color
+    #[cfg(not(feature = "heap_alloc"))]
+    pub fn new(color: Rgb565) -> Self {
+        let mut buffer = [0u8; N];
+        fill_color_444(&mut buffer, color);
+        Self {
+            buffer,
+            rotation: DisplayRotation::default(),
+            geometry: Geometry::PORTRAIT,
+            lines: Self::LINES,
+        }
+    }
+    /// Create a buffer of `N` bytes with a background color
+    #[cfg(feature = "heap_alloc")]
+    pub fn new(mut buffer: Vec<u8>, color: Rgb565) -> Self {
+        if buffer.len() != N {
+            panic!("Incorrect buffer size")
+        }
+        fill_color_444(&mut buffer, color);
+        Self {
+            buffer,
+            rotation: DisplayRotation::default(),
+            geometry: Geometry::PORTRAIT,
+            lines: Self::LINES,
+        }
+    }
+    /// Clear the buffer with a background color
+    pub fn clear_buffer(&mut self, color: Rgb565) {
+        fill_color_444(&mut self.buffer, color);
+    }
+
+    pub fn get_geometry(&self) -> Geometry {
+        self.geometry
+    }
+
+    /// Set the native panel geometry the buffer is laid out for.
+    ///
+    /// # Panics
+    /// Panics if `N` is not a multiple of one packed row of the new geometry or exceeds a full
+    /// frame.
+    pub fn set_geometry(&mut self, geometry: Geometry) {
+        let line_size = PixelFormat::Rgb444.bytes_for(geometry.width as usize);
+        if !N.is_multiple_of(line_size) || N / line_size > geometry.height as usize {
+            panic!("Incorrect buffer size")
+        }
+        self.geometry = geometry;
+        self.lines = (N / line_size) as u16;
+    }
+
+    pub fn get_rotation(&self) -> DisplayRotation {
+        self.rotation
+    }
+
+    pub fn set_rotation(&mut self, rotation: DisplayRotation) {
+        self.rotation = rotation
+    }
+
     fn set_pixel(&mut self, x: u16, y: u16, color: Rgb565) {
-        if x >= COLS || y >= ROWS {
+        let size = self.size();
+        if x as u32 >= size.width || y as u32 >= size.height {
             return;
         }
-        let idx = self.get_location(x, y);
-        let color = color.into_storage();
-        self.buffer[idx] = (color >> 8) as u8;
-        self.buffer[idx + 1] = color as u8;
+        let width = self.geometry.width as usize;
+        let (x, y) = rotate(
+            self.rotation,
+            x as usize,
+            y as usize,
+            width,
+            self.lines as usize,
+        );
+        let index = y * width + x;
+        let idx = index / 2 * 3;
+        let [r, g, b] = to_444(color);
+        // An even pixel owns the first byte and the high nibble of the shared middle byte; an
+        // odd pixel owns the low nibble of the middle byte and the last byte.
+        if index.is_multiple_of(2) {
+            self.buffer[idx] = r << 4 | g;
+            self.buffer[idx + 1] = b << 4 | (self.buffer[idx + 1] & 0x0F);
+        } else {
+            self.buffer[idx + 1] = (self.buffer[idx + 1] & 0xF0) | r;
+            self.buffer[idx + 2] = g << 4 | b;
+        }
+    }
+}
+
+impl<const N: usize> Framebuffer for Display2in14Rgb444<N> {
+    fn bytes(&self) -> &[u8] {
+        &self.buffer
+    }
+    fn area(&self) -> (u16, u16, u16, u16) {
+        (0, 0, self.geometry.width - 1, self.lines - 1)
+    }
+    fn pixel_format(&self) -> PixelFormat {
+        PixelFormat::Rgb444
     }
 }
 
-impl OriginDimensions for Display2in14 {
+impl<const N: usize> OriginDimensions for Display2in14Rgb444<N> {
     fn size(&self) -> Size {
         match self.get_rotation() {
             DisplayRotation::Rotate0 | DisplayRotation::Rotate180 => {
-                Size::new(COLS as u32, ROWS as u32)
+                Size::new(self.geometry.width as u32, self.lines as u32)
             }
             DisplayRotation::Rotate90 | DisplayRotation::Rotate270 => {
-                Size::new(ROWS as u32, COLS as u32)
+                Size::new(self.lines as u32, self.geometry.width as u32)
+            }
+        }
+    }
+}
+
+impl<const N: usize> DrawTarget for Display2in14Rgb444<N> {
+    type Color = Rgb565;
+
+    type Error = DisplayError;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        pixels.into_iter().try_for_each(|pixel| {
+            let ((x, y), color) = ((pixel.0.x as u16, pixel.0.y as u16), pixel.1);
+            self.set_pixel(x, y, color);
+            Ok(())
+        })
+    }
+}
+
+/// Colors a [`Display2in14Packed`] stores in fewer bits than RGB565.
+///
+/// They are converted to RGB565 with [`Into`] only when the buffer is sent to the panel.
+pub trait PackedColor: PixelColor + Into<Rgb565> {
+    /// Bits per pixel: 1, 2, 4 or 8
+    const BITS: usize;
+
+    /// The color's `BITS`-bit representation
+    fn to_bits(self) -> u8;
+    /// Decodes a `BITS`-bit representation
+    fn from_bits(bits: u8) -> Self;
+}
+
+impl PackedColor for BinaryColor {
+    const BITS: usize = 1;
+
+    fn to_bits(self) -> u8 {
+        self.is_on() as u8
+    }
+    fn from_bits(bits: u8) -> Self {
+        BinaryColor::from(bits != 0)
+    }
+}
+
+impl PackedColor for Gray8 {
+    const BITS: usize = 8;
+
+    fn to_bits(self) -> u8 {
+        self.luma()
+    }
+    fn from_bits(bits: u8) -> Self {
+        Gray8::new(bits)
+    }
+}
+
+/// Framebuffer holding `N` bytes of [`PackedColor`] pixels, converted to RGB565 on flush.
+///
+/// Monochrome or grayscale UIs (menus, text) draw in `BinaryColor` or `Gray8` and need a
+/// sixteenth or a half of the RAM of a [`Display2in14`]: a full portrait frame is 9600 bytes in
+/// `BinaryColor`. Pixels are packed along the rows of its [`Geometry`] (portrait unless changed
+/// with [`set_geometry`](Self::set_geometry)), the first pixel in the most significant bits of
+/// each byte. Send it with
+/// [`Lcd::flush_packed`](crate::driver::Lcd::flush_packed), which converts while streaming.
+///
+/// As with [`Display2in14`], a smaller `N` gives a strip buffer. `N` must be a non-zero
+/// multiple of one packed portrait row and no larger than a full frame.
+pub struct Display2in14Packed<C, const N: usize> {
+    #[cfg(not(feature = "heap_alloc"))]
+    pub(crate) buffer: [u8; N],
+    #[cfg(feature = "heap_alloc")]
+    pub(crate) buffer: Vec<u8>,
+    rotation: DisplayRotation,
+    geometry: Geometry,
+    /// Number of rows held by the buffer for the current geometry
+    lines: u16,
+    _color: PhantomData<C>,
+}
+
+impl<C: PackedColor, const N: usize> Display2in14Packed<C, N> {
+    /// Bytes in one portrait row
+    const LINE_SIZE: usize = frame_size(COLS, 1, C::BITS as u8);
+    /// Number of portrait rows covered by the buffer
+    const LINES: u16 = {
+        assert!(
+            matches!(C::BITS, 1 | 2 | 4 | 8),
+            "Packed colors must have 1, 2, 4 or 8 bits"
+        );
+        assert!(N > 0, "Framebuffer size must not be zero");
+        assert!(
+            N.is_multiple_of(Self::LINE_SIZE),
+            "Framebuffer size must be a multiple of one row"
+        );
+        assert!(
+            N <= Self::LINE_SIZE * ROWS as usize,
+            "Framebuffer size must not exceed a full frame"
+        );
+        (N / Self::LINE_SIZE) as u16
+    };
+
+    /// Create a buffer of `N` bytes with a background color
+    #[cfg(not(feature = "heap_alloc"))]
+    pub fn new(color: C) -> Self {
+        let mut display = Self {
+            buffer: [0u8; N],
+            rotation: DisplayRotation::default(),
+            geometry: Geometry::PORTRAIT,
+            lines: Self::LINES,
+            _color: PhantomData,
+        };
+        display.clear_buffer(color);
+        display
+    }
+    /// Create a buffer of `N` bytes with a background color
+    #[cfg(feature = "heap_alloc")]
+    pub fn new(buffer: Vec<u8>, color: C) -> Self {
+        if buffer.len() != N {
+            panic!("Incorrect buffer size")
+        }
+        let mut display = Self {
+            buffer,
+            rotation: DisplayRotation::default(),
+            geometry: Geometry::PORTRAIT,
+            lines: Self::LINES,
+            _color: PhantomData,
+        };
+        display.clear_buffer(color);
+        display
+    }
+    /// Clear the buffer with a background color
+    pub fn clear_buffer(&mut self, color: C) {
+        let bits = color.to_bits();
+        let byte = (0..8 / C::BITS).fold(0u16, |byte, _| byte << C::BITS | bits as u16) as u8;
+        self.buffer.fill(byte);
+    }
+
+    pub fn get_geometry(&self) -> Geometry {
+        self.geometry
+    }
+
+    /// Set the native panel geometry the buffer is laid out for.
+    ///
+    /// # Panics
+    /// Panics if `N` is not a multiple of one packed row of the new geometry or exceeds a full
+    /// frame.
+    pub fn set_geometry(&mut self, geometry: Geometry) {
+        let line_size = geometry.width as usize * C::BITS / 8;
+        if !N.is_multiple_of(line_size) || N / line_size > geometry.height as usize {
+            panic!("Incorrect buffer size")
+        }
+        self.geometry = geometry;
+        self.lines = (N / line_size) as u16;
+    }
+
+    pub fn get_rotation(&self) -> DisplayRotation {
+        self.rotation
+    }
+
+    pub fn set_rotation(&mut self, rotation: DisplayRotation) {
+        self.rotation = rotation
+    }
+
+    /// Number of rows held by the buffer in its current geometry
+    pub fn lines(&self) -> u16 {
+        self.lines
+    }
+
+    /// Every pixel converted to RGB565, in row order as it is sent to the panel
+    pub fn rgb565_pixels(&self) -> impl Iterator<Item = Rgb565> + '_ {
+        let per_byte = 8 / C::BITS;
+        let mask = ((1u16 << C::BITS) - 1) as u8;
+        self.buffer.iter().flat_map(move |&byte| {
+            (0..per_byte).map(move |i| {
+                let shift = 8 - C::BITS * (i + 1);
+                C::from_bits(byte >> shift & mask).into()
+            })
+        })
+    }
+
+    fn set_pixel(&mut self, x: u16, y: u16, color: C) {
+        let size = self.size();
+        if x as u32 >= size.width || y as u32 >= size.height {
+            return;
+        }
+        let width = self.geometry.width as usize;
+        let (x, y) = rotate(
+            self.rotation,
+            x as usize,
+            y as usize,
+            width,
+            self.lines as usize,
+        );
+        let bit = (y * width + x) * C::BITS;
+        let shift = 8 - C::BITS - bit % 8;
+        let mask = (((1u16 << C::BITS) - 1) as u8) << shift;
+        let byte = &mut self.buffer[bit / 8];
+        *byte = (*byte & !mask) | (color.to_bits() << shift & mask);
+    }
+}
+
+impl<C: PackedColor, const N: usize> OriginDimensions for Display2in14Packed<C, N> {
+    fn size(&self) -> Size {
+        match self.get_rotation() {
+            DisplayRotation::Rotate0 | DisplayRotation::Rotate180 => {
+                Size::new(self.geometry.width as u32, self.lines as u32)
+            }
+            DisplayRotation::Rotate90 | DisplayRotation::Rotate270 => {
+                Size::new(self.lines as u32, self.geometry.width as u32)
+            }
+        }
+    }
+}
+
+impl<C: PackedColor, const N: usize> DrawTarget for Display2in14Packed<C, N> {
+    type Color = C;
+
+    type Error = DisplayError;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        pixels.into_iter().try_for_each(|pixel| {
+            if pixel.0.x >= 0 && pixel.0.y >= 0 {
+                self.set_pixel(pixel.0.x as u16, pixel.0.y as u16, pixel.1);
             }
+            Ok(())
+        })
+    }
+}
+
+/// Framebuffer covering only a `width` x `height` window of the panel.
+///
+/// The window is anchored at a fixed `(x, y)` offset on the panel, and drawing coordinates are
+/// relative to that origin. `N` must equal `width * height * 2`. Flush it with
+/// [`Lcd::flush_window`](crate::driver::Lcd::flush_window).
+pub struct WindowBuffer<const N: usize> {
+    #[cfg(not(feature = "heap_alloc"))]
+    pub(crate) buffer: [u8; N],
+    #[cfg(feature = "heap_alloc")]
+    pub(crate) buffer: Vec<u8>,
+    x: u16,
+    y: u16,
+    width: u16,
+    height: u16,
+    /// Byte order colors are stored in
+    byte_order: ByteOrder,
+}
+
+impl<const N: usize> WindowBuffer<N> {
+    /// Create a window buffer at `(x, y)` with a background color
+    ///
+    /// # Panics
+    /// Panics if `N` is not `width * height * 2` or the window does not fit on the panel in
+    /// either [`Geometry`].
+    #[cfg(not(feature = "heap_alloc"))]
+    pub fn new(x: u16, y: u16, width: u16, height: u16, color: Rgb565) -> Self {
+        Self::check_window(x, y, width, height);
+        let mut buffer = [0u8; N];
+        fill_color(&mut buffer, color, ByteOrder::default());
+        Self {
+            buffer,
+            x,
+            y,
+            width,
+            height,
+            byte_order: ByteOrder::default(),
+        }
+    }
+    /// Create a window buffer at `(x, y)` with a background color
+    ///
+    /// # Panics
+    /// Panics if `buffer` or `N` is not `width * height * 2` bytes long, or the window does not
+    /// fit on the panel in either [`Geometry`].
+    #[cfg(feature = "heap_alloc")]
+    pub fn new(
+        mut buffer: Vec<u8>,
+        x: u16,
+        y: u16,
+        width: u16,
+        height: u16,
+        color: Rgb565,
+    ) -> Self {
+        Self::check_window(x, y, width, height);
+        if buffer.len() != N {
+            panic!("Incorrect buffer size")
+        }
+        fill_color(&mut buffer, color, ByteOrder::default());
+        Self {
+            buffer,
+            x,
+            y,
+            width,
+            height,
+            byte_order: ByteOrder::default(),
+        }
+    }
+    fn check_window(x: u16, y: u16, width: u16, height: u16) {
+        if frame_size(width, height, 16) != N {
+            panic!("Incorrect buffer size")
+        }
+        let fits = |geometry: Geometry| {
+            x as u32 + width as u32 <= geometry.width as u32
+                && y as u32 + height as u32 <= geometry.height as u32
+        };
+        if width == 0 || height == 0 || !(fits(Geometry::PORTRAIT) || fits(Geometry::LANDSCAPE)) {
+            panic!("Window out of bounds")
         }
     }
+    /// Clear the buffer with a background color
+    pub fn clear_buffer(&mut self, color: Rgb565) {
+        fill_color(&mut self.buffer, color, self.byte_order);
+    }
+    /// Panel coordinates of the window's top-left corner
+    pub fn origin(&self) -> (u16, u16) {
+        (self.x, self.y)
+    }
+    pub fn get_byte_order(&self) -> ByteOrder {
+        self.byte_order
+    }
+    /// Set the byte order used for colors drawn from now on (existing pixels are not converted)
+    pub fn set_byte_order(&mut self, byte_order: ByteOrder) {
+        self.byte_order = byte_order
+    }
+    fn set_pixel(&mut self, x: u16, y: u16, color: Rgb565) {
+        if x >= self.width || y >= self.height {
+            return;
+        }
+        let idx = (y as usize * self.width as usize + x as usize) * 2;
+        let bytes = self.byte_order.bytes(color.into_storage());
+        self.buffer[idx..idx + 2].copy_from_slice(&bytes);
+    }
+}
+
+impl<const N: usize> Framebuffer for WindowBuffer<N> {
+    fn bytes(&self) -> &[u8] {
+        &self.buffer
+    }
+    fn area(&self) -> (u16, u16, u16, u16) {
+        (
+            self.x,
+            self.y,
+            self.x + self.width - 1,
+            self.y + self.height - 1,
+        )
+    }
+}
+
+impl<const N: usize> OriginDimensions for WindowBuffer<N> {
+    fn size(&self) -> Size {
+        Size::new(self.width as u32, self.height as u32)
+    }
 }
 
-impl DrawTarget for Display2in14 {
+impl<const N: usize> DrawTarget for WindowBuffer<N> {
     type Color = Rgb565;
 
-    type Error = display_interface::DisplayError;
+    type Error = DisplayError;
 
     fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
     where
@@ -159,3 +1777,451 @@ impl DrawTarget for Display2in14 {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(feature = "heap_alloc")]
+    use alloc::vec;
+    use embedded_graphics::primitives::PrimitiveStyle;
+
+    /// Decodes the pixel at `(x, y)` from a top-down 16-bit BMP produced by `to_bmp`
+    #[cfg(feature = "heap_alloc")]
+    fn bmp_pixel(bmp: &[u8], x: usize, y: usize) -> u16 {
+        let read_u32 = |at: usize| u32::from_le_bytes(bmp[at..at + 4].try_into().unwrap());
+        let offset = read_u32(10) as usize;
+        let width = read_u32(18) as usize;
+        let stride = (width * 2).next_multiple_of(4);
+        let at = offset + y * stride + x * 2;
+        u16::from_le_bytes([bmp[at], bmp[at + 1]])
+    }
+
+    #[test]
+    #[cfg(feature = "heap_alloc")]
+    fn bmp_matches_rotated_framebuffer() {
+        let mut display = Display2in14::new(vec![0; FRAME_SIZE], Rgb565::BLUE);
+        display.set_rotation(DisplayRotation::Rotate90);
+        Rectangle::new(Point::new(10, 20), Size::new(3, 2))
+            .into_styled(PrimitiveStyle::with_fill(Rgb565::RED))
+            .draw(&mut display)
+            .unwrap();
+
+        let bmp = display.to_bmp();
+
+        assert_eq!(&bmp[..2], b"BM");
+        assert_eq!(u32::from_le_bytes(bmp[18..22].try_into().unwrap()), 320);
+        assert_eq!(i32::from_le_bytes(bmp[22..26].try_into().unwrap()), -240);
+        assert_eq!(bmp_pixel(&bmp, 10, 20), Rgb565::RED.into_storage());
+        assert_eq!(bmp_pixel(&bmp, 12, 21), Rgb565::RED.into_storage());
+        assert_eq!(bmp_pixel(&bmp, 13, 21), Rgb565::BLUE.into_storage());
+        assert_eq!(bmp_pixel(&bmp, 10, 22), Rgb565::BLUE.into_storage());
+    }
+
+    #[test]
+    #[cfg(feature = "stack_alloc")]
+    fn rotated_pixels_land_at_physical_offsets() {
+        use DisplayRotation::*;
+        // Logical pixel and the byte offset of its physical (240x320 portrait) location
+        let cases = [
+            (Rotate0, (0, 0), 0),
+            (Rotate0, (5, 10), (10 * 240 + 5) * 2),
+            (Rotate0, (239, 319), (319 * 240 + 239) * 2),
+            (Rotate90, (0, 0), 239 * 2),
+            (Rotate90, (5, 10), (5 * 240 + 229) * 2),
+            (Rotate90, (319, 239), 319 * 240 * 2),
+            (Rotate180, (0, 0), (319 * 240 + 239) * 2),
+            (Rotate180, (5, 10), (309 * 240 + 234) * 2),
+            (Rotate180, (239, 319), 0),
+            (Rotate270, (0, 0), 319 * 240 * 2),
+            (Rotate270, (5, 10), (314 * 240 + 10) * 2),
+            (Rotate270, (319, 239), 239 * 2),
+        ];
+        let red = Rgb565::RED.into_storage().to_be_bytes();
+        for (rotation, (x, y), offset) in cases {
+            let mut display = Display2in14::new(Rgb565::BLACK);
+            display.set_rotation(rotation);
+            display
+                .draw_iter([Pixel(Point::new(x, y), Rgb565::RED)])
+                .unwrap();
+            let bytes = display.raw_bytes();
+            assert_eq!(bytes[offset..offset + 2], red, "{x},{y} at {offset}");
+            assert_eq!(bytes.iter().filter(|&&b| b != 0).count(), 1);
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "stack_alloc")]
+    fn gradient_stays_vertical_when_rotated() {
+        use DisplayRotation::*;
+        let top = Rgb565::new(0, 0, 31);
+        let bottom = Rgb565::new(31, 0, 0);
+        for rotation in [Rotate0, Rotate90, Rotate180, Rotate270] {
+            let mut display = Display2in14::new(Rgb565::BLACK);
+            display.set_rotation(rotation);
+            display.fill_gradient(top, bottom);
+            let size = display.size();
+            let color_at = |x: u32, y: u32| {
+                let idx = display.get_location(x as u16, y as u16);
+                u16::from_be_bytes([display.buffer[idx], display.buffer[idx + 1]])
+            };
+            let last = size.height - 1;
+            assert_eq!(color_at(0, 0), top.into_storage());
+            assert_eq!(color_at(size.width - 1, 0), top.into_storage());
+            assert_eq!(color_at(0, last), bottom.into_storage());
+            assert_eq!(color_at(size.width - 1, last), bottom.into_storage());
+            assert_eq!(color_at(0, last / 2), color_at(size.width - 1, last / 2));
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "stack_alloc")]
+    fn rounded_rect_cuts_corners_and_keeps_edges() {
+        let mut display = Display2in14::new(Rgb565::BLACK);
+        display.fill_rounded_rect(
+            Rectangle::new(Point::new(10, 10), Size::new(40, 20)),
+            8,
+            Rgb565::RED,
+        );
+        let is_red = |x: u16, y: u16| {
+            let idx = display.get_location(x, y);
+            display.buffer[idx..idx + 2] == Rgb565::RED.into_storage().to_be_bytes()
+        };
+        assert!(!is_red(10, 10) && !is_red(49, 10) && !is_red(10, 29) && !is_red(49, 29));
+        assert!(is_red(18, 10) && is_red(41, 10) && is_red(10, 18) && is_red(49, 21));
+        assert!(is_red(30, 20));
+        assert!(!is_red(9, 20) && !is_red(50, 20) && !is_red(30, 30));
+    }
+
+    #[test]
+    #[cfg(feature = "stack_alloc")]
+    fn bounding_box_covers_exactly_the_buffer() {
+        use DisplayRotation::*;
+        let red = Rgb565::RED.into_storage().to_be_bytes();
+        let all_red = |bytes: &[u8]| bytes.chunks_exact(2).all(|pixel| pixel == red);
+        for geometry in [Geometry::PORTRAIT, Geometry::LANDSCAPE] {
+            for rotation in [Rotate0, Rotate90, Rotate180, Rotate270] {
+                let mut strip = Display2in14::<{ LINE_SIZE * 16 }>::new_strip(Rgb565::BLACK);
+                strip.set_geometry(geometry);
+                strip.set_rotation(rotation);
+                let bounds = strip.bounding_box();
+                bounds
+                    .into_styled(PrimitiveStyle::with_fill(Rgb565::RED))
+                    .draw(&mut strip)
+                    .unwrap();
+                assert!(all_red(strip.raw_bytes()));
+                // Anything outside the bounding box is clipped rather than wrapped or panicking
+                strip.clear_buffer(Rgb565::BLACK);
+                bounds
+                    .offset(4)
+                    .into_styled(PrimitiveStyle::with_fill(Rgb565::RED))
+                    .draw(&mut strip)
+                    .unwrap();
+                assert!(all_red(strip.raw_bytes()));
+            }
+        }
+
+        let mut window = WindowBuffer::<{ 10 * 8 * 2 }>::new(100, 200, 10, 8, Rgb565::BLACK);
+        assert_eq!(
+            window.bounding_box(),
+            Rectangle::new(Point::zero(), Size::new(10, 8))
+        );
+        window
+            .bounding_box()
+            .offset(4)
+            .into_styled(PrimitiveStyle::with_fill(Rgb565::RED))
+            .draw(&mut window)
+            .unwrap();
+        assert!(all_red(window.bytes()));
+        assert_eq!(window.area(), (100, 200, 109, 207));
+    }
+
+    #[test]
+    #[cfg(feature = "stack_alloc")]
+    fn mono_bitmap_expands_bits_and_clips() {
+        let mut display = Display2in14::new(Rgb565::BLUE);
+        // 10 pixels wide: two bytes per row, the last six bits are padding
+        let bits = [0b1000_0000, 0b0100_0000, 0b0000_0001, 0b0000_0000];
+        display.draw_mono_bitmap(Point::new(-1, 0), 10, &bits, Rgb565::WHITE, None);
+        display.draw_mono_bitmap(
+            Point::new(100, 50),
+            10,
+            &bits,
+            Rgb565::WHITE,
+            Some(Rgb565::BLACK),
+        );
+        let color_at = |x: u16, y: u16| {
+            let idx = display.get_location(x, y);
+            u16::from_be_bytes([display.buffer[idx], display.buffer[idx + 1]])
+        };
+        assert_eq!(color_at(0, 0), Rgb565::BLUE.into_storage());
+        assert_eq!(color_at(8, 0), Rgb565::WHITE.into_storage());
+        assert_eq!(color_at(6, 1), Rgb565::WHITE.into_storage());
+        assert_eq!(color_at(5, 1), Rgb565::BLUE.into_storage());
+        assert_eq!(color_at(100, 50), Rgb565::WHITE.into_storage());
+        assert_eq!(color_at(101, 50), Rgb565::BLACK.into_storage());
+        assert_eq!(color_at(109, 51), Rgb565::BLACK.into_storage());
+        assert_eq!(color_at(110, 51), Rgb565::BLUE.into_storage());
+    }
+
+    #[test]
+    #[cfg(all(feature = "stack_alloc", feature = "default_font"))]
+    fn text_with_bg_covers_the_whole_cell() {
+        let mut display = Display2in14::new(Rgb565::RED);
+        display
+            .draw_text_with_bg(20, 40, "8", Rgb565::WHITE, Rgb565::BLACK)
+            .unwrap();
+        let bounds = display
+            .draw_text_with_bg(20, 40, "1", Rgb565::BLUE, Rgb565::BLACK)
+            .unwrap();
+        let color_at = |point: Point| {
+            let idx = display.get_location(point.x as u16, point.y as u16);
+            let raw = u16::from_be_bytes([display.buffer[idx], display.buffer[idx + 1]]);
+            Rgb565::from(embedded_graphics::pixelcolor::raw::RawU16::new(raw))
+        };
+        assert!(bounds.points().any(|p| color_at(p) == Rgb565::BLUE));
+        assert!(
+            bounds
+                .points()
+                .all(|p| matches!(color_at(p), Rgb565::BLUE | Rgb565::BLACK)),
+            "no pixel of the previous glyph or the clear color survives"
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "stack_alloc")]
+    fn from_raw_copies_the_image() {
+        let image: [u8; LINE_SIZE] = core::array::from_fn(|i| i as u8);
+        let display = Display2in14::<LINE_SIZE>::from_raw(&image);
+        assert_eq!(display.raw_bytes(), &image);
+    }
+
+    #[test]
+    #[cfg(feature = "stack_alloc")]
+    #[should_panic(expected = "Incorrect buffer size")]
+    fn from_raw_rejects_a_wrong_size() {
+        Display2in14::<LINE_SIZE>::from_raw(&[0; LINE_SIZE - 2]);
+    }
+
+    #[test]
+    fn frame_size_counts_bits_per_pixel() {
+        assert_eq!(LINE_SIZE, 480);
+        assert_eq!(FRAME_SIZE_444, 240 * 320 * 3 / 2);
+        assert_eq!(frame_size(240, 320, 18), 540 * 320);
+    }
+
+    #[test]
+    #[should_panic(expected = "byte boundary")]
+    fn frame_size_rejects_partial_bytes() {
+        frame_size(3, 1, 12);
+    }
+
+    #[test]
+    #[cfg(feature = "stack_alloc")]
+    fn blend_region_mixes_each_channel_and_clips() {
+        let mut display = Display2in14::new(Rgb565::new(31, 63, 0));
+        display.set_rotation(DisplayRotation::Rotate90);
+        display.blend_region(
+            Rectangle::new(Point::new(310, 230), Size::new(20, 20)),
+            Rgb565::new(1, 1, 31),
+            128,
+        );
+        let color_at = |x: u16, y: u16| {
+            let idx = display.get_location(x, y);
+            u16::from_be_bytes([display.buffer[idx], display.buffer[idx + 1]])
+        };
+        // 31 + (1 - 31) * 128 / 255 = 16, 63 + (1 - 63) * 128 / 255 = 32, 31 * 128 / 255 = 15
+        assert_eq!(color_at(319, 239), Rgb565::new(16, 32, 15).into_storage());
+        assert_eq!(color_at(310, 230), Rgb565::new(16, 32, 15).into_storage());
+        assert_eq!(color_at(309, 239), Rgb565::new(31, 63, 0).into_storage());
+    }
+
+    #[test]
+    #[cfg(feature = "stack_alloc")]
+    fn replace_pixel_returns_the_previous_color() {
+        let mut display = Display2in14::new(Rgb565::BLUE);
+        display.set_rotation(DisplayRotation::Rotate270);
+        display.set_byte_order(ByteOrder::LsbFirst);
+        display.clear_buffer(Rgb565::BLUE);
+        assert_eq!(
+            display.replace_pixel(5, 10, Rgb565::RED),
+            Some(Rgb565::BLUE)
+        );
+        assert_eq!(
+            display.replace_pixel(5, 10, Rgb565::BLUE),
+            Some(Rgb565::RED)
+        );
+        assert_eq!(display.replace_pixel(320, 0, Rgb565::RED), None);
+    }
+
+    #[test]
+    #[cfg(feature = "heap_alloc")]
+    fn try_new_rejects_a_wrong_buffer_length() {
+        assert!(matches!(
+            Display2in14::try_new(vec![0; FRAME_SIZE - 2], Rgb565::BLACK),
+            Err(DisplayError::InvalidFormatError)
+        ));
+        let buffer = vec![0; LINE_SIZE * 2];
+        let strip = Display2in14::<{ LINE_SIZE * 2 }>::try_new_strip(buffer, Rgb565::RED).unwrap();
+        assert_eq!(
+            strip.raw_bytes()[..2],
+            Rgb565::RED.into_storage().to_be_bytes()
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "stack_alloc")]
+    fn circle_and_arc_cover_their_boundary_pixels() {
+        let mut display = Display2in14::<{ LINE_SIZE * 20 }>::new_strip(Rgb565::BLACK);
+        display.set_rotation(DisplayRotation::Rotate90);
+        // Clipped at the left edge of the rotated strip
+        display.draw_filled_circle(Point::new(2, 10), 3, Rgb565::RED);
+        assert_eq!(display.pixel(5, 10), Some(Rgb565::RED));
+        assert_eq!(display.pixel(2, 7), Some(Rgb565::RED));
+        assert_eq!(display.pixel(4, 12), Some(Rgb565::RED));
+        assert_eq!(display.pixel(5, 12), Some(Rgb565::BLACK));
+        assert_eq!(display.pixel(6, 10), Some(Rgb565::BLACK));
+        assert_eq!(display.pixel(2, 6), Some(Rgb565::BLACK));
+
+        // Lower half of a ring two pixels wide
+        let center = Point::new(10, 40);
+        display.draw_arc(center, 5, 0, 180, 2, Rgb565::GREEN);
+        assert_eq!(display.pixel(10, 45), Some(Rgb565::GREEN));
+        assert_eq!(display.pixel(10, 44), Some(Rgb565::GREEN));
+        assert_eq!(display.pixel(10, 43), Some(Rgb565::BLACK));
+        assert_eq!(display.pixel(15, 40), Some(Rgb565::GREEN));
+        assert_eq!(display.pixel(5, 40), Some(Rgb565::GREEN));
+        assert_eq!(display.pixel(10, 35), Some(Rgb565::BLACK));
+        assert_eq!(display.pixel(14, 37), Some(Rgb565::BLACK));
+
+        // A 270 degree gauge scale leaves only the bottom quarter empty
+        display.draw_arc(Point::new(10, 70), 5, 135, 405, 1, Rgb565::BLUE);
+        assert_eq!(display.pixel(10, 65), Some(Rgb565::BLUE));
+        assert_eq!(display.pixel(15, 70), Some(Rgb565::BLUE));
+        assert_eq!(display.pixel(10, 75), Some(Rgb565::BLACK));
+    }
+
+    #[test]
+    #[cfg(feature = "stack_alloc")]
+    fn image_raw_rejects_partial_rows() {
+        let mut display = Display2in14::<{ LINE_SIZE * 4 }>::new_strip(Rgb565::BLACK);
+        let data = [0xF8, 0x00, 0xF8, 0x00, 0xF8];
+        assert!(display.draw_image_raw(Point::zero(), &data, 2).is_err());
+        assert!(
+            display
+                .draw_image_raw(Point::zero(), &data[..4], 0)
+                .is_err()
+        );
+        assert_eq!(display.pixel(0, 0), Some(Rgb565::BLACK));
+        display
+            .draw_image_raw(Point::new(1, 2), &data[..4], 2)
+            .unwrap();
+        assert_eq!(display.pixel(2, 2), Some(Rgb565::RED));
+    }
+
+    #[test]
+    #[cfg(feature = "stack_alloc")]
+    fn image_and_blit_honor_lsb_first_byte_order() {
+        let mut display = Display2in14::<{ LINE_SIZE * 4 }>::new_strip(Rgb565::BLACK);
+        display.set_byte_order(ByteOrder::LsbFirst);
+        let red = Rgb565::RED.into_storage().to_be_bytes();
+        display
+            .draw_image_raw(Point::new(3, 1), &[red, red].concat(), 2)
+            .unwrap();
+        assert_eq!(display.pixel(3, 1), Some(Rgb565::RED));
+        assert_eq!(display.pixel(4, 1), Some(Rgb565::RED));
+
+        let mut src = Display2in14::<{ LINE_SIZE * 4 }>::new_strip(Rgb565::BLUE);
+        src.draw_iter([Pixel(Point::new(0, 0), Rgb565::GREEN)])
+            .unwrap();
+        let rect = Rectangle::new(Point::zero(), Size::new(2, 1));
+        display.blit(&src, rect, Point::new(10, 2));
+        assert_eq!(display.pixel(10, 2), Some(Rgb565::GREEN));
+        assert_eq!(display.pixel(11, 2), Some(Rgb565::BLUE));
+    }
+
+    #[test]
+    #[cfg(feature = "stack_alloc")]
+    fn pixel_reads_back_through_rotation_and_byte_order() {
+        let mut display = Display2in14::<{ LINE_SIZE * 4 }>::new_strip(Rgb565::BLACK);
+        display.set_byte_order(ByteOrder::LsbFirst);
+        display.set_rotation(DisplayRotation::Rotate270);
+        display
+            .draw_iter([Pixel(Point::new(3, 200), Rgb565::CYAN)])
+            .unwrap();
+        assert_eq!(display.pixel(3, 200), Some(Rgb565::CYAN));
+        assert_eq!(display.pixel(0, 0), Some(Rgb565::BLACK));
+        assert_eq!(display.pixel(4, 0), None);
+        assert_eq!(display.pixel(0, 240), None);
+    }
+
+    #[test]
+    #[cfg(feature = "stack_alloc")]
+    fn progress_bar_fills_from_the_left_and_follows_rotation() {
+        let mut display = Display2in14::<{ LINE_SIZE * 20 }>::new_strip(Rgb565::BLACK);
+        display.set_rotation(DisplayRotation::Rotate180);
+        let rect = Rectangle::new(Point::new(10, 5), Size::new(12, 6));
+        display.draw_progress_bar(rect, 0.25, Rgb565::GREEN, Rgb565::BLUE, Rgb565::WHITE);
+        // 10 inner pixels, so a quarter rounds to 3 filled columns
+        assert_eq!(display.pixel(10, 5), Some(Rgb565::WHITE));
+        assert_eq!(display.pixel(21, 10), Some(Rgb565::WHITE));
+        assert_eq!(display.pixel(11, 6), Some(Rgb565::GREEN));
+        assert_eq!(display.pixel(13, 9), Some(Rgb565::GREEN));
+        assert_eq!(display.pixel(14, 6), Some(Rgb565::BLUE));
+        assert_eq!(display.pixel(20, 9), Some(Rgb565::BLUE));
+        assert_eq!(display.pixel(22, 5), Some(Rgb565::BLACK));
+
+        display.draw_progress_bar(rect, 7.0, Rgb565::GREEN, Rgb565::BLUE, Rgb565::WHITE);
+        assert_eq!(display.pixel(20, 9), Some(Rgb565::GREEN));
+        display.draw_progress_bar(rect, f32::NAN, Rgb565::GREEN, Rgb565::BLUE, Rgb565::WHITE);
+        assert_eq!(display.pixel(11, 6), Some(Rgb565::BLUE));
+    }
+
+    #[test]
+    #[cfg(feature = "stack_alloc")]
+    fn packed_binary_buffer_converts_on_read() {
+        let mut display = Display2in14Packed::<BinaryColor, 60>::new(BinaryColor::Off);
+        assert_eq!(display.size(), Size::new(240, 2));
+        display
+            .draw_iter([
+                Pixel(Point::new(1, 0), BinaryColor::On),
+                Pixel(Point::new(239, 1), BinaryColor::On),
+                Pixel(Point::new(-1, 0), BinaryColor::On),
+            ])
+            .unwrap();
+        assert_eq!(display.buffer[0], 0b0100_0000);
+        assert_eq!(display.buffer[59], 0b0000_0001);
+        let mut pixels = display.rgb565_pixels();
+        assert_eq!(pixels.next(), Some(Rgb565::BLACK));
+        assert_eq!(pixels.next(), Some(Rgb565::WHITE));
+        assert_eq!(pixels.by_ref().filter(|&c| c == Rgb565::WHITE).count(), 1);
+        assert_eq!(display.rgb565_pixels().count(), 480);
+        assert_eq!(display.rgb565_pixels().last(), Some(Rgb565::WHITE));
+    }
+
+    #[test]
+    #[cfg(feature = "stack_alloc")]
+    fn packed_gray_buffer_follows_rotation() {
+        let mut display = Display2in14Packed::<Gray8, 480>::new(Gray8::new(0x10));
+        display.set_rotation(DisplayRotation::Rotate90);
+        display
+            .draw_iter([Pixel(Point::new(0, 0), Gray8::WHITE)])
+            .unwrap();
+        // Logical (0, 0) is the end of the first portrait row when rotated by 90 degrees
+        assert_eq!(display.buffer[239], 0xFF);
+        assert_eq!(display.buffer[0], 0x10);
+    }
+
+    #[test]
+    #[cfg(feature = "stack_alloc")]
+    fn packed_buffer_follows_geometry() {
+        let mut display = Display2in14Packed::<BinaryColor, 120>::new(BinaryColor::Off);
+        assert_eq!(display.lines(), 4);
+        display.set_geometry(Geometry::LANDSCAPE);
+        assert_eq!(display.size(), Size::new(320, 3));
+        display
+            .draw_iter([Pixel(Point::new(0, 1), BinaryColor::On)])
+            .unwrap();
+        // The second landscape row starts 320 bits into the buffer
+        assert_eq!(display.buffer[40], 0b1000_0000);
+    }
+}
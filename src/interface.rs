@@ -1,10 +1,257 @@
 //! Display interface using SPI
+use super::error::{DriverError, Error};
 use super::st7789v::*;
-use display_interface::DisplayError;
-use embedded_hal::{delay::DelayNs, digital::OutputPin, spi::SpiBus};
+use core::convert::Infallible;
+use display_interface::{DataFormat, DisplayError, WriteOnlyDataCommand};
+use embedded_hal::{
+    delay::DelayNs,
+    digital::{self, OutputPin},
+    pwm::SetDutyCycle,
+    spi::{self, SpiBus},
+};
 
-const RESET_DELAY_US: u32 = 12;
-const LCD_ON_DELAY_US: u32 = 1;
+pub(crate) const LCD_ON_DELAY_US: u32 = 1;
+
+/// Bus traffic counters, accumulated while the `stats` feature is enabled.
+#[cfg(feature = "stats")]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct FlushStats {
+    /// Number of bytes clocked over SPI, commands included
+    pub bytes: usize,
+    /// Number of SPI transactions (one per command or read, and one per `SpiBus::write` of
+    /// data, so a write split by `set_max_transfer` counts once per piece)
+    pub transactions: usize,
+}
+
+/// Bus activity reported to the hook set with `set_trace_hook` (`trace` feature).
+#[cfg(feature = "trace")]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TraceEvent {
+    /// A command byte is about to be sent
+    Command(u8),
+    /// This many parameter or pixel bytes are about to be sent
+    Data(usize),
+    /// This many bytes are about to be read back
+    Read(usize),
+}
+
+/// Timing of the hardware reset sequence.
+///
+/// The datasheet requires a low pulse of at least 10 µs and up to 120 ms before commands are
+/// accepted again. Some modules reset reliably only with a longer pulse.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ResetTiming {
+    /// Length of the low pulse on the reset pin, in microseconds
+    pub pulse_us: u32,
+    /// Wait after releasing reset, in milliseconds
+    pub wait_ms: u32,
+}
+
+impl Default for ResetTiming {
+    fn default() -> Self {
+        Self {
+            pulse_us: 15,
+            wait_ms: WAIT_MS,
+        }
+    }
+}
+
+/// Backlight control, either a plain on/off pin or a dimmable PWM channel.
+///
+/// Every [`OutputPin`] is a backlight that is off at level 0 and fully on at any other level.
+/// Wrap a PWM channel in [`PwmBacklight`] for dimming.
+pub trait Backlight {
+    type Error: core::fmt::Debug;
+    /// Whether levels between off and full are visible; if not, fades jump straight to the
+    /// final level
+    const DIMMABLE: bool = true;
+
+    /// Sets the brightness, from 0 (off) to 255 (full)
+    fn set_level(&mut self, level: u8) -> Result<(), Self::Error>;
+}
+
+impl<P: OutputPin> Backlight for P {
+    type Error = P::Error;
+    const DIMMABLE: bool = false;
+
+    fn set_level(&mut self, level: u8) -> Result<(), Self::Error> {
+        if level == 0 {
+            self.set_low()
+        } else {
+            self.set_high()
+        }
+    }
+}
+
+/// The panel's reset line.
+///
+/// Every [`OutputPin`] is a reset pin, active low. Boards that drive reset from a power
+/// supervisor instead of the MCU pass [`NoResetPin`], and the driver falls back to a software
+/// reset (`SWRESET`).
+pub trait ResetPin {
+    type Error: core::fmt::Debug;
+    /// Whether the pin controls the reset line; if not, `SWRESET` is sent instead of a pulse
+    const CONNECTED: bool = true;
+
+    /// Drives the reset line active (low)
+    fn hold(&mut self) -> Result<(), Self::Error>;
+    /// Releases the reset line (high)
+    fn release(&mut self) -> Result<(), Self::Error>;
+}
+
+impl<P: OutputPin> ResetPin for P {
+    type Error = P::Error;
+
+    fn hold(&mut self) -> Result<(), Self::Error> {
+        self.set_low()
+    }
+    fn release(&mut self) -> Result<(), Self::Error> {
+        self.set_high()
+    }
+}
+
+/// Stand-in for a reset line the MCU does not control.
+///
+/// Resets are done with `SWRESET`, after which the controller needs the same stabilization
+/// time as after a hardware reset ([`ResetTiming::wait_ms`]); keep it at the default 120 ms,
+/// since the controller may have been in sleep-out mode.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct NoResetPin;
+
+impl NoResetPin {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl ResetPin for NoResetPin {
+    type Error = core::convert::Infallible;
+    const CONNECTED: bool = false;
+
+    fn hold(&mut self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+    fn release(&mut self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+/// Backlight driven by a PWM channel, with the duty cycle proportional to the level
+pub struct PwmBacklight<P>(pub P);
+
+impl<P: SetDutyCycle> Backlight for PwmBacklight<P> {
+    type Error = P::Error;
+
+    fn set_level(&mut self, level: u8) -> Result<(), Self::Error> {
+        self.0.set_duty_cycle_fraction(level as u16, u8::MAX as u16)
+    }
+}
+
+/// Transport the driver writes commands and data through.
+///
+/// Implemented for every [`SpiBus`] paired with a data/command [`OutputPin`], and for a
+/// `display-interface` [`WriteOnlyDataCommand`] wrapped in [`DiBus`] and paired with
+/// [`NoDcPin`]. The trait is sealed; it only lets the driver treat the two alike.
+pub trait LcdBus<DC: digital::ErrorType>: spi::ErrorType + sealed::Sealed<DC> {
+    /// Selects whether the following writes are data (`true`) or commands (`false`)
+    fn set_dc(&mut self, dc: &mut DC, data: bool) -> Result<(), DC::Error>;
+    /// Writes `bytes` as selected by the last [`set_dc`](Self::set_dc)
+    fn send(&mut self, bytes: &[u8]) -> Result<(), Self::Error>;
+    /// Waits until every written byte has left the bus
+    fn wait_idle(&mut self) -> Result<(), Self::Error>;
+    /// Reads `buf.len()` bytes back from the controller
+    fn receive(&mut self, buf: &mut [u8]) -> Result<(), Self::Error>;
+}
+
+mod sealed {
+    pub trait Sealed<DC> {}
+}
+
+impl<SPI: SpiBus, DC: OutputPin> sealed::Sealed<DC> for SPI {}
+
+impl<SPI: SpiBus, DC: OutputPin> LcdBus<DC> for SPI {
+    fn set_dc(&mut self, dc: &mut DC, data: bool) -> Result<(), DC::Error> {
+        if data { dc.set_high() } else { dc.set_low() }
+    }
+    fn send(&mut self, bytes: &[u8]) -> Result<(), Self::Error> {
+        self.write(bytes)
+    }
+    fn wait_idle(&mut self) -> Result<(), Self::Error> {
+        self.flush()
+    }
+    fn receive(&mut self, buf: &mut [u8]) -> Result<(), Self::Error> {
+        self.read(buf)
+    }
+}
+
+/// Error of a [`DiBus`]: the error reported by the wrapped interface, or
+/// [`DisplayError::BusWriteError`] for reads, which a write-only interface cannot do
+#[derive(Clone, Debug)]
+pub struct InterfaceError(pub DisplayError);
+
+impl spi::Error for InterfaceError {
+    fn kind(&self) -> spi::ErrorKind {
+        spi::ErrorKind::Other
+    }
+}
+
+/// A `display-interface` [`WriteOnlyDataCommand`] used as the bus of
+/// [`Lcd`](crate::driver::Lcd), with [`NoDcPin`] in place of the data/command pin.
+///
+/// Command bytes go to `send_commands` and parameter and pixel bytes to `send_data`. Reads
+/// fail with [`InterfaceError`], so methods that read registers back are unavailable through
+/// it.
+pub struct DiBus<DI> {
+    di: DI,
+    /// Whether the following writes are data
+    data: bool,
+}
+
+impl<DI: WriteOnlyDataCommand> DiBus<DI> {
+    pub fn new(di: DI) -> Self {
+        Self { di, data: false }
+    }
+    /// Returns the wrapped interface
+    pub fn release(self) -> DI {
+        self.di
+    }
+}
+
+impl<DI> spi::ErrorType for DiBus<DI> {
+    type Error = InterfaceError;
+}
+
+impl<DI: WriteOnlyDataCommand> sealed::Sealed<NoDcPin> for DiBus<DI> {}
+
+impl<DI: WriteOnlyDataCommand> LcdBus<NoDcPin> for DiBus<DI> {
+    fn set_dc(&mut self, _dc: &mut NoDcPin, data: bool) -> Result<(), Infallible> {
+        self.data = data;
+        Ok(())
+    }
+    fn send(&mut self, bytes: &[u8]) -> Result<(), Self::Error> {
+        let words = DataFormat::U8(bytes);
+        if self.data {
+            self.di.send_data(words)
+        } else {
+            self.di.send_commands(words)
+        }
+        .map_err(InterfaceError)
+    }
+    fn wait_idle(&mut self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+    fn receive(&mut self, _buf: &mut [u8]) -> Result<(), Self::Error> {
+        Err(InterfaceError(DisplayError::BusWriteError))
+    }
+}
+
+/// Stand-in for the data/command pin when a [`DiBus`] selects commands and data itself.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct NoDcPin;
+
+impl digital::ErrorType for NoDcPin {
+    type Error = Infallible;
+}
 
 pub struct DisplayInterface<SPI, RST, WR, PWR> {
     /// SPI device
@@ -15,21 +262,101 @@ pub struct DisplayInterface<SPI, RST, WR, PWR> {
     wr: WR,
     /// LCD backlight control pin (Low: Off, High: On)
     pwr: PWR,
+    /// Timing used by `reset`
+    reset_timing: ResetTiming,
+    /// Longest slice passed to a single `SpiBus::write`
+    max_transfer: usize,
+    /// Wait after switching the backlight, in microseconds
+    lcd_on_delay_us: u32,
+    /// Traffic since the last `take_stats`
+    #[cfg(feature = "stats")]
+    stats: FlushStats,
+    /// Called before each command, data and read transaction
+    #[cfg(feature = "trace")]
+    trace_hook: Option<fn(TraceEvent)>,
 }
 
 impl<SPI, RST, WR, PWR> DisplayInterface<SPI, RST, WR, PWR> {
     /// Create and initialize display
     pub fn new(spi: SPI, rst: RST, wr: WR, pwr: PWR) -> Self {
-        Self { spi, rst, wr, pwr }
+        Self {
+            spi,
+            rst,
+            wr,
+            pwr,
+            reset_timing: ResetTiming::default(),
+            max_transfer: usize::MAX,
+            lcd_on_delay_us: LCD_ON_DELAY_US,
+            #[cfg(feature = "stats")]
+            stats: FlushStats::default(),
+            #[cfg(feature = "trace")]
+            trace_hook: None,
+        }
+    }
+    /// Sets the timing used by `reset` and `try_reset`
+    pub fn set_reset_timing(&mut self, timing: ResetTiming) {
+        self.reset_timing = timing;
+    }
+    /// Returns the timing used by `reset` and `try_reset`
+    pub fn reset_timing(&self) -> ResetTiming {
+        self.reset_timing
+    }
+    /// Limits how many bytes are passed to a single `SpiBus::write`.
+    ///
+    /// Some HALs or DMA backends cap the length of one transfer and truncate longer ones;
+    /// with a limit set, data is split into writes of at most `max_transfer` bytes. The
+    /// default is no limit. A limit of zero is treated as one byte.
+    pub fn set_max_transfer(&mut self, max_transfer: usize) {
+        self.max_transfer = max_transfer.max(1);
+    }
+    /// Sets how long `lcd_on` and `lcd_off` wait after switching the backlight, in
+    /// microseconds. The default is 1 µs.
+    pub fn set_lcd_on_delay(&mut self, delay_us: u32) {
+        self.lcd_on_delay_us = delay_us;
+    }
+    /// Returns how long `lcd_on` and `lcd_off` wait after switching the backlight, in
+    /// microseconds
+    pub fn lcd_on_delay(&self) -> u32 {
+        self.lcd_on_delay_us
+    }
+    /// Returns the traffic counted since the last call and resets the counters.
+    #[cfg(feature = "stats")]
+    pub fn take_stats(&mut self) -> FlushStats {
+        core::mem::take(&mut self.stats)
+    }
+    /// Sets a function called with every command byte and data length before it is sent, or
+    /// removes it with `None`.
+    #[cfg(feature = "trace")]
+    pub fn set_trace_hook(&mut self, hook: Option<fn(TraceEvent)>) {
+        self.trace_hook = hook;
+    }
+    /// Reports `event` to the trace hook, if any
+    #[cfg(feature = "trace")]
+    #[inline(always)]
+    fn trace(&self, event: TraceEvent) {
+        if let Some(hook) = self.trace_hook {
+            hook(event);
+        }
+    }
+    /// Counts one transaction of `bytes` bytes
+    #[inline(always)]
+    fn record(&mut self, bytes: usize) {
+        #[cfg(feature = "stats")]
+        {
+            self.stats.bytes += bytes;
+            self.stats.transactions += 1;
+        }
+        #[cfg(not(feature = "stats"))]
+        let _ = bytes;
     }
 }
 
 impl<SPI, RST, WR, PWR> DisplayInterface<SPI, RST, WR, PWR>
 where
-    SPI: SpiBus,
-    RST: OutputPin,
-    WR: OutputPin,
-    PWR: OutputPin,
+    SPI: LcdBus<WR>,
+    RST: ResetPin,
+    WR: digital::ErrorType,
+    PWR: Backlight,
 {
     /// Sends a command byte synchronously over SPI.
     ///
@@ -38,12 +365,13 @@ where
     /// to complete by flushing the SPI buffer. The function blocks until the command is fully
     /// transmitted.
     #[inline]
-    pub fn cmd(&mut self, command: u8) -> Result<(), DisplayError> {
-        self.wr.set_low().map_err(|_| DisplayError::DCError)?;
-        self.spi
-            .write(&[command])
-            .map_err(|_| DisplayError::BusWriteError)?;
-        self.spi.flush().map_err(|_| DisplayError::BusWriteError)
+    pub fn cmd(&mut self, command: u8) -> Result<(), DriverError<SPI, RST, WR, PWR>> {
+        self.record(1);
+        #[cfg(feature = "trace")]
+        self.trace(TraceEvent::Command(command));
+        self.spi.set_dc(&mut self.wr, false).map_err(Error::Pin)?;
+        self.spi.send(&[command]).map_err(Error::Spi)?;
+        self.spi.wait_idle().map_err(Error::Spi)
     }
     /// Sends an array of data bytes synchronously over SPI.
     ///
@@ -51,12 +379,36 @@ where
     /// to indicate data, writes the provided data bytes to the SPI bus, and waits for the operation
     /// to complete by flushing the SPI buffer. The function blocks until the data is fully transmitted.
     #[inline]
-    pub fn data(&mut self, data: &[u8]) -> Result<(), DisplayError> {
-        self.wr.set_high().map_err(|_| DisplayError::DCError)?;
-        self.spi
-            .write(data)
-            .map_err(|_| DisplayError::BusWriteError)?;
-        self.spi.flush().map_err(|_| DisplayError::BusWriteError)
+    pub fn data(&mut self, data: &[u8]) -> Result<(), DriverError<SPI, RST, WR, PWR>> {
+        self.data_async(data)?;
+        self.spi.wait_idle().map_err(Error::Spi)
+    }
+    /// Sends an array of data bytes with DC high, without waiting for them to leave the bus.
+    ///
+    /// Use it for a burst of slices that [`data`](Self::data) would flush one by one: queue
+    /// them with this method and call [`flush`](Self::flush) once before DC next changes, so a
+    /// DMA-backed `SpiBus` can pipeline the transfers.
+    #[inline]
+    pub fn data_nowait(&mut self, data: &[u8]) -> Result<(), DriverError<SPI, RST, WR, PWR>> {
+        self.data_async(data)
+    }
+    /// Sends a command byte followed by its parameter bytes.
+    ///
+    /// DC is driven low for the command and high for `data`. Because `SpiBus::write` may
+    /// return before the last bits are clocked out, the command byte is flushed before DC
+    /// changes; the data is then written and flushed once. Nothing is sent after the command if
+    /// `data` is empty.
+    pub fn write_command(
+        &mut self,
+        command: u8,
+        data: &[u8],
+    ) -> Result<(), DriverError<SPI, RST, WR, PWR>> {
+        self.cmd(command)?;
+        if data.is_empty() {
+            return Ok(());
+        }
+        self.data_async(data)?;
+        self.flush()
     }
     /// Sends a command byte asynchronously over SPI.
     ///
@@ -66,11 +418,12 @@ where
     /// **Note:** Ensure that all commands are fully sent before calling this function again or changing
     /// the state of the `wr` pin to prevent peripheral misinterpretation.
     #[inline]
-    pub fn cmd_async(&mut self, command: u8) -> Result<(), DisplayError> {
-        self.wr.set_low().map_err(|_| DisplayError::DCError)?;
-        self.spi
-            .write(&[command])
-            .map_err(|_| DisplayError::BusWriteError)
+    pub fn cmd_async(&mut self, command: u8) -> Result<(), DriverError<SPI, RST, WR, PWR>> {
+        self.record(1);
+        #[cfg(feature = "trace")]
+        self.trace(TraceEvent::Command(command));
+        self.spi.set_dc(&mut self.wr, false).map_err(Error::Pin)?;
+        self.spi.send(&[command]).map_err(Error::Spi)
     }
     /// Sends an array of data bytes asynchronously over SPI.
     ///
@@ -80,11 +433,32 @@ where
     /// **Note:** Ensure that all data is fully sent before calling this function again or changing
     /// the state of the `wr` pin to prevent peripheral misinterpretation.
     #[inline]
-    pub fn data_async(&mut self, data: &[u8]) -> Result<(), DisplayError> {
-        self.wr.set_high().map_err(|_| DisplayError::DCError)?;
-        self.spi
-            .write(data)
-            .map_err(|_| DisplayError::BusWriteError)
+    pub fn data_async(&mut self, data: &[u8]) -> Result<(), DriverError<SPI, RST, WR, PWR>> {
+        #[cfg(feature = "trace")]
+        self.trace(TraceEvent::Data(data.len()));
+        self.spi.set_dc(&mut self.wr, true).map_err(Error::Pin)?;
+        self.write_chunked(data)
+    }
+    /// Sends more data bytes asynchronously over SPI, assuming DC is already high.
+    ///
+    /// Behaves like [`data_async`](Self::data_async) but skips driving the data/command (DC)
+    /// line, saving a GPIO write per call in tight streaming loops.
+    ///
+    /// **Note:** Only call this after `data` or `data_async` with no command in between;
+    /// otherwise the bytes are taken as commands.
+    #[inline]
+    pub fn data_continue(&mut self, data: &[u8]) -> Result<(), DriverError<SPI, RST, WR, PWR>> {
+        #[cfg(feature = "trace")]
+        self.trace(TraceEvent::Data(data.len()));
+        self.write_chunked(data)
+    }
+    /// Writes `data` in pieces of at most `max_transfer` bytes, counting each as a transaction
+    fn write_chunked(&mut self, data: &[u8]) -> Result<(), DriverError<SPI, RST, WR, PWR>> {
+        for chunk in data.chunks(self.max_transfer) {
+            self.record(chunk.len());
+            self.spi.send(chunk).map_err(Error::Spi)?;
+        }
+        Ok(())
     }
     /// Waits until all commands or data have been sent over SPI.
     ///
@@ -93,27 +467,124 @@ where
     /// may change after data transmission. Calling this function helps avoid data corruption or
     /// unexpected peripheral behavior.
     #[inline]
-    pub fn flush(&mut self) -> Result<(), DisplayError> {
-        self.spi.flush().map_err(|_| DisplayError::BusWriteError)
+    pub fn flush(&mut self) -> Result<(), DriverError<SPI, RST, WR, PWR>> {
+        self.spi.wait_idle().map_err(Error::Spi)
+    }
+    /// Reads `buf.len()` bytes back from the controller after sending a command.
+    ///
+    /// The command byte is sent with the data/command (DC) line low, then DC is driven high and
+    /// `buf.len()` bytes are clocked in with `SpiBus::read`, filling `buf`. Any dummy clock cycle
+    /// the command requires (see the datasheet) shows up as a leading byte in `buf`.
+    ///
+    /// **Note:** This requires the controller's serial output to be connected to MISO (or the
+    /// shared SDA line to be readable by the SPI peripheral).
+    pub fn read(
+        &mut self,
+        command: u8,
+        buf: &mut [u8],
+    ) -> Result<(), DriverError<SPI, RST, WR, PWR>> {
+        self.cmd(command)?;
+        self.record(buf.len());
+        #[cfg(feature = "trace")]
+        self.trace(TraceEvent::Read(buf.len()));
+        self.spi.set_dc(&mut self.wr, true).map_err(Error::Pin)?;
+        self.spi.receive(buf).map_err(Error::Spi)?;
+        self.spi.wait_idle().map_err(Error::Spi)
+    }
+    /// Sets the backlight brightness, from 0 (off) to 255 (full)
+    ///
+    /// A plain GPIO backlight is on for any non-zero level.
+    #[inline]
+    pub fn set_backlight(&mut self, level: u8) -> Result<(), DriverError<SPI, RST, WR, PWR>> {
+        self.pwr.set_level(level).map_err(Error::Backlight)
+    }
+    /// Ramps the backlight from `from` to `to` in `steps` equal steps spread over
+    /// `duration_ms`.
+    ///
+    /// A backlight that is not [dimmable](Backlight::DIMMABLE), or a fade of zero steps, is
+    /// switched to `to` at once without waiting.
+    pub fn fade_backlight(
+        &mut self,
+        from: u8,
+        to: u8,
+        steps: u16,
+        duration_ms: u32,
+        delay: &mut impl DelayNs,
+    ) -> Result<(), DriverError<SPI, RST, WR, PWR>> {
+        if !PWR::DIMMABLE || steps == 0 {
+            return self.set_backlight(to);
+        }
+        let step_us = (duration_ms as u64 * 1000 / steps as u64) as u32;
+        let span = to as i32 - from as i32;
+        self.set_backlight(from)?;
+        for step in 1..=steps as i32 {
+            delay.delay_us(step_us);
+            self.set_backlight((from as i32 + span * step / steps as i32) as u8)?;
+        }
+        Ok(())
     }
     /// Reset the device
+    ///
+    /// # Panics
+    ///
+    /// Panics if a pin operation fails; use [`try_reset`](Self::try_reset) when the pin HAL
+    /// can report errors.
     #[inline]
     pub fn reset(&mut self, delay: &mut impl DelayNs) {
-        self.rst.set_low().unwrap();
-        delay.delay_us(RESET_DELAY_US);
-        self.rst.set_high().unwrap();
-        delay.delay_ms(WAIT_MS);
+        self.try_reset(delay).unwrap();
+    }
+    /// Reset the device, returning pin errors instead of panicking
+    #[inline]
+    pub fn try_reset(
+        &mut self,
+        delay: &mut impl DelayNs,
+    ) -> Result<(), DriverError<SPI, RST, WR, PWR>> {
+        if RST::CONNECTED {
+            self.rst.hold().map_err(Error::Reset)?;
+            delay.delay_us(self.reset_timing.pulse_us);
+            self.rst.release().map_err(Error::Reset)?;
+        } else {
+            self.cmd(Cmd::SWRESET.bits())?;
+        }
+        delay.delay_ms(self.reset_timing.wait_ms);
+        Ok(())
     }
     /// LCD on
+    ///
+    /// # Panics
+    ///
+    /// Panics if the backlight pin fails; see [`try_lcd_on`](Self::try_lcd_on).
     #[inline]
     pub fn lcd_on(&mut self, delay: &mut impl DelayNs) {
-        self.pwr.set_high().unwrap();
-        delay.delay_us(LCD_ON_DELAY_US);
+        self.try_lcd_on(delay).unwrap();
+    }
+    /// LCD on, returning pin errors instead of panicking
+    #[inline]
+    pub fn try_lcd_on(
+        &mut self,
+        delay: &mut impl DelayNs,
+    ) -> Result<(), DriverError<SPI, RST, WR, PWR>> {
+        self.pwr.set_level(u8::MAX).map_err(Error::Backlight)?;
+        delay.delay_us(self.lcd_on_delay_us);
+        Ok(())
     }
     /// LCD off
+    ///
+    /// # Panics
+    ///
+    /// Panics if the backlight pin fails; see [`try_lcd_off`](Self::try_lcd_off).
     #[inline]
     pub fn lcd_off(&mut self, delay: &mut impl DelayNs) {
-        self.pwr.set_low().unwrap();
-        delay.delay_us(LCD_ON_DELAY_US);
+        self.try_lcd_off(delay).unwrap();
+    }
+    /// LCD off, returning pin errors instead of panicking
+    #[inline]
+    pub fn try_lcd_off(
+        &mut self,
+        delay: &mut impl DelayNs,
+    ) -> Result<(), DriverError<SPI, RST, WR, PWR>> {
+        self.pwr.set_level(0).map_err(Error::Backlight)?;
+        delay.delay_us(self.lcd_on_delay_us);
+        Ok(())
     }
 }
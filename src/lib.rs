@@ -2,7 +2,29 @@
 
 #![no_std]
 
+// The stack-allocated framebuffer code is gated on `not(feature = "heap_alloc")` rather than on
+// `stack_alloc`, so exactly one variant is compiled for every feature combination and the
+// `compile_error!`s below are the only errors reported.
+#[cfg(all(feature = "stack_alloc", feature = "heap_alloc"))]
+compile_error!(
+    "Features `stack_alloc` and `heap_alloc` are mutually exclusive. `heap_alloc` is a default \
+     feature, so depend on this crate with `default-features = false, features = [\"stack_alloc\"]` \
+     to use the stack framebuffer."
+);
+
+#[cfg(not(any(feature = "stack_alloc", feature = "heap_alloc")))]
+compile_error!(
+    "Either feature `stack_alloc` or `heap_alloc` must be enabled to select where the \
+     framebuffer is allocated."
+);
+
+#[cfg(feature = "async")]
+pub mod async_driver;
+pub mod console;
 pub mod driver;
+pub mod error;
 pub mod graphics;
-mod interface;
-mod st7789v;
\ No newline at end of file
+pub mod interface;
+#[cfg(feature = "simulator")]
+pub mod simulator;
+mod st7789v;
@@ -0,0 +1,254 @@
+//! Host-side model of the panel for developing without hardware
+extern crate std;
+
+use core::convert::Infallible;
+use std::vec;
+use std::vec::Vec;
+
+use embedded_graphics::{
+    pixelcolor::{Rgb565, raw::RawU16},
+    prelude::*,
+};
+
+use super::error::Error;
+use super::graphics::{BMP_HEADER_SIZE, Framebuffer, Geometry, bmp_header};
+use super::st7789v::{COLS, ROWS, frame_size};
+
+/// Error type of the simulator, which never fails on I/O
+pub type SimulatorError = Error<Infallible, Infallible>;
+
+/// In-memory stand-in for [`Lcd`](crate::driver::Lcd) that models the controller's frame memory.
+///
+/// It offers the same drawing and flushing methods, so UI code can be exercised on a desktop
+/// and inspected with [`pixel`](Self::pixel) or [`to_bmp`](Self::to_bmp). Writes follow the
+/// controller's addressing: pixel data fills the current frame area row by row and wraps back
+/// to its start.
+pub struct SimulatorLcd {
+    /// Frame memory, one RGB565 value per pixel in native (portrait) order
+    gram: Vec<u16>,
+    geometry: Geometry,
+    /// Current frame area as `(start_x, start_y, end_x, end_y)`, inclusive
+    area: (u16, u16, u16, u16),
+}
+
+impl Default for SimulatorLcd {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SimulatorLcd {
+    /// Create a simulated panel with black frame memory and portrait geometry
+    pub fn new() -> Self {
+        Self {
+            gram: vec![0; COLS as usize * ROWS as usize],
+            geometry: Geometry::PORTRAIT,
+            area: (0, 0, COLS - 1, ROWS - 1),
+        }
+    }
+    /// Returns the native geometry the panel is addressed with.
+    pub fn geometry(&self) -> Geometry {
+        self.geometry
+    }
+    /// Selects portrait or landscape as the native addressing of the panel.
+    pub fn set_geometry(&mut self, geometry: Geometry) -> Result<(), SimulatorError> {
+        self.geometry = geometry;
+        Ok(())
+    }
+    /// Sets the frame memory area that following writes fill.
+    pub fn set_frame_area(
+        &mut self,
+        start_x: u16,
+        start_y: u16,
+        end_x: u16,
+        end_y: u16,
+    ) -> Result<(), SimulatorError> {
+        self.area = (start_x, start_y, end_x, end_y);
+        Ok(())
+    }
+    /// Writes one raw RGB565 pixel.
+    pub fn set_pixel(&mut self, x: u16, y: u16, color: u16) -> Result<(), SimulatorError> {
+        self.set_frame_area(x, y, x, y)?;
+        self.write(&color.to_be_bytes());
+        Ok(())
+    }
+    /// Writes a raw RGB565 image into a rectangular window, as
+    /// [`Lcd::draw_image`](crate::driver::Lcd::draw_image) does.
+    pub fn draw_image(
+        &mut self,
+        x: u16,
+        y: u16,
+        width: u16,
+        height: u16,
+        data: &[u8],
+    ) -> Result<(), SimulatorError> {
+        if data.len() != frame_size(width, height, 16) {
+            return Err(Error::InvalidFormat);
+        }
+        if width == 0 || height == 0 {
+            return Ok(());
+        }
+        if x as u32 + width as u32 > self.geometry.width as u32
+            || y as u32 + height as u32 > self.geometry.height as u32
+        {
+            return Err(Error::OutOfBounds);
+        }
+        self.set_frame_area(x, y, x + width - 1, y + height - 1)?;
+        self.write(data);
+        Ok(())
+    }
+    /// Fills the whole panel with one raw RGB565 color.
+    pub fn fill_frame(&mut self, color: u16) -> Result<(), SimulatorError> {
+        self.gram.fill(color);
+        Ok(())
+    }
+    /// Sends the content of any [`Framebuffer`] to the simulated panel.
+    pub fn flush(&mut self, framebuffer: &impl Framebuffer) -> Result<(), SimulatorError> {
+        let (start_x, start_y, end_x, end_y) = framebuffer.area();
+        self.set_frame_area(start_x, start_y, end_x, end_y)?;
+        self.write(framebuffer.bytes());
+        Ok(())
+    }
+    /// Color of the pixel at `(x, y)` in the current geometry, or `None` outside the panel
+    pub fn pixel(&self, x: u16, y: u16) -> Option<Rgb565> {
+        if x >= self.geometry.width || y >= self.geometry.height {
+            return None;
+        }
+        let raw = self.gram[y as usize * self.geometry.width as usize + x as usize];
+        Some(Rgb565::from(RawU16::new(raw)))
+    }
+    /// Encodes the panel content as a top-down 16-bit RGB565 BMP image.
+    pub fn to_bmp(&self) -> Vec<u8> {
+        let Geometry { width, height } = self.geometry;
+        let mut bmp = Vec::with_capacity(BMP_HEADER_SIZE + self.gram.len() * 2);
+        bmp.extend_from_slice(&bmp_header(width as u32, height as u32));
+        self.gram
+            .iter()
+            .for_each(|pixel| bmp.extend_from_slice(&pixel.to_le_bytes()));
+        bmp
+    }
+    /// Streams big-endian RGB565 bytes into the current frame area
+    fn write(&mut self, data: &[u8]) {
+        let (start_x, start_y, end_x, end_y) = self.area;
+        let width = self.geometry.width as usize;
+        let mut x = start_x;
+        let mut y = start_y;
+        for pixel in data.chunks_exact(2) {
+            if x < self.geometry.width && y < self.geometry.height {
+                self.gram[y as usize * width + x as usize] =
+                    u16::from_be_bytes([pixel[0], pixel[1]]);
+            }
+            if x < end_x {
+                x += 1;
+            } else {
+                x = start_x;
+                y = if y < end_y { y + 1 } else { start_y };
+            }
+        }
+    }
+}
+
+impl OriginDimensions for SimulatorLcd {
+    fn size(&self) -> Size {
+        Size::new(self.geometry.width as u32, self.geometry.height as u32)
+    }
+}
+
+impl DrawTarget for SimulatorLcd {
+    type Color = Rgb565;
+
+    type Error = SimulatorError;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        for Pixel(point, color) in pixels {
+            if point.x < 0
+                || point.y < 0
+                || point.x >= self.geometry.width as i32
+                || point.y >= self.geometry.height as i32
+            {
+                continue;
+            }
+            self.set_pixel(point.x as u16, point.y as u16, color.into_storage())?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Big-endian bytes of `colors`
+    fn bytes<const N: usize>(colors: [Rgb565; N]) -> Vec<u8> {
+        colors
+            .iter()
+            .flat_map(|color| color.into_storage().to_be_bytes())
+            .collect()
+    }
+
+    #[test]
+    #[cfg(feature = "stack_alloc")]
+    fn flush_places_a_strip_at_the_top() {
+        use crate::graphics::{Display2in14, LINE_SIZE};
+
+        let mut strip = Display2in14::<{ LINE_SIZE * 2 }>::new_strip(Rgb565::BLUE);
+        strip
+            .draw_iter([Pixel(Point::new(5, 1), Rgb565::RED)])
+            .unwrap();
+        let mut lcd = SimulatorLcd::new();
+        lcd.flush(&strip).unwrap();
+        assert_eq!(lcd.pixel(0, 0), Some(Rgb565::BLUE));
+        assert_eq!(lcd.pixel(5, 1), Some(Rgb565::RED));
+        assert_eq!(lcd.pixel(239, 1), Some(Rgb565::BLUE));
+        assert_eq!(lcd.pixel(5, 2), Some(Rgb565::BLACK));
+    }
+
+    #[test]
+    fn image_fills_its_window_row_by_row() {
+        let mut lcd = SimulatorLcd::new();
+        let image = bytes([Rgb565::RED, Rgb565::GREEN, Rgb565::BLUE, Rgb565::WHITE]);
+        lcd.draw_image(10, 20, 2, 2, &image).unwrap();
+        assert_eq!(lcd.pixel(10, 20), Some(Rgb565::RED));
+        assert_eq!(lcd.pixel(11, 20), Some(Rgb565::GREEN));
+        assert_eq!(lcd.pixel(10, 21), Some(Rgb565::BLUE));
+        assert_eq!(lcd.pixel(11, 21), Some(Rgb565::WHITE));
+        assert_eq!(lcd.pixel(12, 20), Some(Rgb565::BLACK));
+        assert_eq!(lcd.pixel(10, 22), Some(Rgb565::BLACK));
+    }
+
+    #[test]
+    fn writes_past_the_window_wrap_to_its_start() {
+        let mut lcd = SimulatorLcd::new();
+        lcd.set_frame_area(10, 20, 11, 21).unwrap();
+        lcd.write(&bytes([
+            Rgb565::RED,
+            Rgb565::RED,
+            Rgb565::RED,
+            Rgb565::RED,
+            Rgb565::YELLOW,
+        ]));
+        assert_eq!(lcd.pixel(10, 20), Some(Rgb565::YELLOW));
+        assert_eq!(lcd.pixel(11, 20), Some(Rgb565::RED));
+        assert_eq!(lcd.pixel(11, 21), Some(Rgb565::RED));
+        assert_eq!(lcd.pixel(12, 20), Some(Rgb565::BLACK));
+    }
+
+    #[test]
+    fn landscape_geometry_addresses_320_columns() {
+        let mut lcd = SimulatorLcd::new();
+        let image = bytes([Rgb565::RED]);
+        assert!(matches!(
+            lcd.draw_image(300, 10, 1, 1, &image),
+            Err(Error::OutOfBounds)
+        ));
+        assert_eq!(lcd.pixel(300, 10), None);
+        lcd.set_geometry(Geometry::LANDSCAPE).unwrap();
+        assert_eq!(lcd.size(), Size::new(320, 240));
+        lcd.draw_image(300, 10, 1, 1, &image).unwrap();
+        assert_eq!(lcd.pixel(300, 10), Some(Rgb565::RED));
+        assert_eq!(lcd.pixel(10, 300), None);
+    }
+}
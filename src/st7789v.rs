@@ -5,15 +5,58 @@ use bitflags::bitflags;
 pub const COLS: u16 = 240;
 pub const ROWS: u16 = 320;
 
+/// Wait after a hardware reset before commands are accepted (datasheet worst case)
 pub const WAIT_MS: u32 = 120;
 
-pub const FRAME_SIZE: usize = (COLS as usize) * (ROWS as usize) * 2;
+/// Number of bytes in a `width` x `height` buffer with `bits_per_pixel` bits per pixel.
+///
+/// Every row must end on a byte boundary, as the controller expects for packed formats such
+/// as RGB444. Fails to compile when used in a constant whose rows are not whole bytes or whose
+/// size overflows `usize`, and panics at run time otherwise.
+pub const fn frame_size(width: u16, height: u16, bits_per_pixel: u8) -> usize {
+    let row_bits = match (width as usize).checked_mul(bits_per_pixel as usize) {
+        Some(bits) => bits,
+        None => panic!("frame size overflows usize"),
+    };
+    if row_bits % 8 != 0 {
+        panic!("rows do not end on a byte boundary");
+    }
+    match (row_bits / 8).checked_mul(height as usize) {
+        Some(size) => size,
+        None => panic!("frame size overflows usize"),
+    }
+}
+
+/// Size of one portrait row in RGB565
+pub const LINE_SIZE: usize = frame_size(COLS, 1, 16);
+
+/// Size of a full portrait frame in RGB565
+pub const FRAME_SIZE: usize = frame_size(COLS, ROWS, 16);
+
+/// Size of one portrait row in the packed 12-bit (RGB444) format
+pub const LINE_SIZE_444: usize = frame_size(COLS, 1, 12);
+
+/// Size of a full frame in the packed 12-bit (RGB444) format
+pub const FRAME_SIZE_444: usize = frame_size(COLS, ROWS, 12);
+
+/// Size of the longest row in either orientation
+pub const MAX_LINE_SIZE: usize = frame_size(ROWS, 1, 16);
 
 bitflags! {
     pub struct Cmd: u8 {
         const NOP = 0x00;
+        /// Software Reset
+        const SWRESET = 0x01;
+        /// Read Display Power Mode
+        const RDDPM = 0x0A;
+        /// Read Display MADCTL
+        const RDDMADCTL = 0x0B;
+        /// Read Display Self-Diagnostic Result
+        const RDDSDR = 0x0F;
         /// Sleep Out
         const SLPOUT = 0x11;
+        /// Display Inversion Off
+        const INVOFF = 0x20;
         /// Display Inversion On
         const INVON = 0x21;
         /// Display On
@@ -24,11 +67,67 @@ bitflags! {
         const RASET = 0x2B;
         /// Transfer data from MCU to frame memory
         const RAMWR = 0x2C;
+        /// Transfer data from frame memory to MCU
+        const RAMRD = 0x2E;
+        /// Vertical Scrolling Definition
+        const VSCRDEF = 0x33;
+        /// Tearing Effect Line Off
+        const TEOFF = 0x34;
+        /// Tearing Effect Line On
+        const TEON = 0x35;
         /// Memory Data Access Control
         const MADCTL = 0x36;
+        /// Vertical Scroll Start Address of RAM
+        const VSCRSADD = 0x37;
         /// Interface Pixel Format
         const COLMOD = 0x3A;
+        /// Porch Setting
+        const PORCTRL = 0xB2;
+        /// VCOM Setting
+        const VCOMS = 0xBB;
+        /// VDV and VRH Command Enable
+        const VDVVRHEN = 0xC2;
+        /// VRH Set
+        const VRHS = 0xC3;
+        /// VDV Set
+        const VDVS = 0xC4;
+        /// Frame Rate Control in Normal Mode
+        const FRCTRL2 = 0xC6;
+        /// Power Control 1
+        const PWCTRL1 = 0xD0;
     }
 }
 
+bitflags! {
+    /// Bits of the Memory Data Access Control register (`MADCTL`, 0x36).
+    ///
+    /// Combine them and pass `.bits()` to [`Lcd::mem_data_ac`](crate::driver::Lcd::mem_data_ac).
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    pub struct Madctl: u8 {
+        /// Page address order: bottom to top
+        const MY = 0x80;
+        /// Column address order: right to left
+        const MX = 0x40;
+        /// Page/column exchange (landscape addressing)
+        const MV = 0x20;
+        /// Line address order: LCD refresh bottom to top
+        const ML = 0x10;
+        /// BGR color order
+        const BGR = 0x08;
+        /// Display data latch order: LCD refresh right to left
+        const MH = 0x04;
+    }
+}
 
+bitflags! {
+    /// Self-diagnostic result reported by `RDDSDR` (0x0F).
+    ///
+    /// Both bits are set after a power-up or reset whose checks passed.
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    pub struct SelfDiagnostic: u8 {
+        /// Registers were loaded from NVM correctly
+        const REGISTER_LOADING = 0x80;
+        /// The display functions were detected as working
+        const FUNCTIONALITY = 0x40;
+    }
+}